@@ -1,10 +1,9 @@
 use iced::widget::{button, column, container, text};
-use iced::{executor, theme, Application, Command, Element, Length, Settings, Theme, Vector};
-
+use iced::{executor, theme, Application, Command, Element, Length, Settings, Size, Theme, Vector};
 use iced_graph_editor::widget::graph;
-use iced_graph_editor::widget::graph::editor;
-
-use self::node::Node;
+use iced_graph_editor::widget::graph::edge::Endpoint;
+use iced_graph_editor::widget::graph::model::{self, Id};
+use iced_graph_editor::widget::graph::node::{Port, Side};
 
 mod node;
 
@@ -16,17 +15,15 @@ fn main() {
     .unwrap()
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum Message {
-    Graph(editor::Event),
+    Graph(graph::editor::Event<Id>),
     ToggleTheme,
-    DeleteNode(usize),
+    DeleteNode(Id),
 }
 
 struct App {
-    nodes: Vec<Node>,
-    scaling: f32,
-    translation: Vector,
+    model: graph::Model<node::Kind, ()>,
     theme: Theme,
 }
 
@@ -37,34 +34,25 @@ impl Application for App {
     type Flags = ();
 
     fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        let nodes = vec![
-            Node {
-                kind: node::Kind::A,
-                offset: Vector::new(50.0, 50.0),
-                edges: vec![1],
-            },
-            Node {
-                kind: node::Kind::B,
-                offset: Vector::new(150.0, 100.0),
-                edges: vec![2, 3],
-            },
-            Node {
-                kind: node::Kind::C,
-                offset: Vector::new(350.0, 25.0),
-                edges: vec![3],
-            },
-            Node {
-                kind: node::Kind::D,
-                offset: Vector::new(500.0, 200.0),
-                edges: vec![],
-            },
-        ];
+        let mut model = graph::Model::new();
+
+        let a = model.add_node(model::Node::new(node::Kind::A, Vector::new(50.0, 50.0)));
+        let b = model.add_node(model::Node::new(node::Kind::B, Vector::new(150.0, 100.0)));
+        let c = model.add_node(model::Node::new(node::Kind::C, Vector::new(350.0, 25.0)));
+        let d = model.add_node(
+            model::Node::new(node::Kind::D, Vector::new(500.0, 200.0))
+                .inputs([Port::new("a", Side::Left), Port::new("b", Side::Top)]),
+        );
+
+        model.connect(Endpoint::new(a, 0), Endpoint::new(b, 0), ());
+        model.connect(Endpoint::new(b, 0), Endpoint::new(c, 0), ());
+        model.connect(Endpoint::new(b, 0), Endpoint::new(d, 0), ());
+        model.connect(Endpoint::new(c, 0), Endpoint::new(d, 1), ());
+        model.clear_history();
 
         (
             App {
-                nodes,
-                scaling: 1.0,
-                translation: Vector::new(0.0, 0.0),
+                model,
                 theme: Theme::Light,
             },
             Command::none(),
@@ -81,24 +69,11 @@ impl Application for App {
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
-            Message::Graph(event) => match event {
-                editor::Event::NodeMoved { index, offset } => {
-                    self.nodes[index].offset = offset;
-
-                    Command::none()
-                }
-                editor::Event::Scaled(scaling, translation) => {
-                    self.scaling = scaling;
-                    self.translation = translation;
+            Message::Graph(event) => {
+                self.model.update(event);
 
-                    Command::none()
-                }
-                editor::Event::Translated(translation) => {
-                    self.translation = translation;
-
-                    Command::none()
-                }
-            },
+                Command::none()
+            }
             Message::ToggleTheme => {
                 match &self.theme {
                     Theme::Light => self.theme = Theme::Dark,
@@ -108,15 +83,8 @@ impl Application for App {
 
                 Command::none()
             }
-            Message::DeleteNode(index) => {
-                self.nodes.remove(index);
-                self.nodes.iter_mut().for_each(|node| {
-                    node.edges = std::mem::take(&mut node.edges)
-                        .into_iter()
-                        .filter(|i| *i != index)
-                        .map(|i| if i > index { i - 1 } else { i })
-                        .collect();
-                });
+            Message::DeleteNode(id) => {
+                self.model.remove_node(id);
 
                 Command::none()
             }
@@ -124,7 +92,7 @@ impl Application for App {
     }
 
     fn view(&self) -> Element<Message> {
-        let node_content = |kind: node::Kind| -> Element<_> {
+        let node_content = |id: Id, kind: &node::Kind| -> Element<_> {
             match kind {
                 node::Kind::A => text("Node A").into(),
                 node::Kind::B => column![text("Node B"), text("Some description...")]
@@ -132,7 +100,7 @@ impl Application for App {
                     .into(),
                 node::Kind::C => column![
                     text("Node C"),
-                    button(text("Delete")).on_press(Message::DeleteNode(2))
+                    button(text("Delete")).on_press(Message::DeleteNode(id))
                 ]
                 .spacing(5)
                 .into(),
@@ -145,17 +113,12 @@ impl Application for App {
             }
         };
 
-        let nodes = self
-            .nodes
-            .iter()
-            .map(|node| graph::Node::new(node_content(node.kind), node.offset, node.edges.clone()))
-            .collect();
-
         container(
             container(
-                graph::Editor::new(nodes, Message::Graph)
-                    .scaling(self.scaling)
-                    .translation(self.translation),
+                self.model
+                    .editor(node_content, |_, edge| edge, Message::Graph)
+                    .snap_to_grid(20.0)
+                    .minimap(Size::new(200.0, 150.0)),
             )
             .width(Length::Fill)
             .height(Length::Fill)
@@ -1,5 +1,3 @@
-use iced::Vector;
-
 #[derive(Debug, Clone, Copy)]
 pub enum Kind {
     A,
@@ -7,10 +5,3 @@ pub enum Kind {
     C,
     D,
 }
-
-#[derive(Debug, Clone)]
-pub struct Node {
-    pub kind: Kind,
-    pub offset: Vector,
-    pub edges: Vec<usize>,
-}
@@ -1,6 +1,7 @@
+use iced_core::Color;
 use iced_style::Theme;
 
-use crate::widget::graph::{editor, node};
+use crate::widget::graph::{edge, editor, node};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub enum Node {
@@ -19,9 +20,21 @@ impl node::StyleSheet for Theme {
                 border_radius: 3.0,
                 border_width: 1.0,
                 border_color: self.extended_palette().background.strong.color,
+                port_radius: 4.0,
+                port_color: self.palette().background,
+                port_border_width: 1.0,
+                port_border_color: self.extended_palette().background.strong.color,
             },
         }
     }
+
+    fn selected(&self, style: Self::Style) -> node::Appearance {
+        node::Appearance {
+            border_width: 2.0,
+            border_color: self.palette().primary,
+            ..node::StyleSheet::appearance(self, style)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -42,6 +55,35 @@ impl editor::StyleSheet for Theme {
                 border_color: self.extended_palette().background.strong.color,
                 connector_width: 2.0,
                 connector_color: self.palette().text,
+                hovered_connector_color: self.extended_palette().primary.weak.color,
+                selected_connector_color: self.palette().primary,
+                selection_background: Color {
+                    a: 0.2,
+                    ..self.palette().primary
+                }
+                .into(),
+                selection_border_color: self.palette().primary,
+                grid: editor::Grid::Lines,
+                grid_spacing: 20.0,
+                grid_subdivisions: 5,
+                grid_color: Color {
+                    a: 0.3,
+                    ..self.extended_palette().background.strong.color
+                },
+                grid_major_color: Color {
+                    a: 0.7,
+                    ..self.extended_palette().background.strong.color
+                },
+                minimap_background: self.palette().background,
+                minimap_node_color: self.extended_palette().background.strong.color,
+                minimap_edge_color: self.extended_palette().background.weak.color,
+                minimap_viewport_color: self.palette().primary,
+                source_marker: edge::Marker::None,
+                target_marker: edge::Marker::Arrow,
+                marker_size: 8.0,
+                label_text_color: self.palette().text,
+                label_background: self.palette().background.into(),
+                label_border_color: self.extended_palette().background.strong.color,
             },
         }
     }
@@ -1,5 +1,14 @@
+pub mod bindings;
+pub mod edge;
 pub mod editor;
+pub mod model;
 pub mod node;
 
+mod history;
+mod minimap;
+
+pub use bindings::Bindings;
+pub use edge::Edge;
 pub use editor::Editor;
+pub use model::Model;
 pub use node::Node;
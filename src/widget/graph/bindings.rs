@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use iced_native::keyboard::{KeyCode, Modifiers};
+use iced_native::mouse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chord {
+    Key(KeyCode, Modifiers),
+    Mouse(mouse::Button, Modifiers),
+    Wheel(Modifiers),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Pan,
+    Select,
+    Zoom,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    ResetView,
+    Fit,
+    Undo,
+    Redo,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Navigation {
+    #[default]
+    Mouse,
+    Trackpad,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    chords: HashMap<Chord, Action>,
+    navigation: Navigation,
+    // Clicking nodes, edges and labels, dragging from ports and navigating
+    // the minimap take any modifiers, so they use a button rather than chords
+    button: mouse::Button,
+    toggle: Modifiers,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self {
+            chords: HashMap::new(),
+            navigation: Navigation::default(),
+            button: mouse::Button::Left,
+            toggle: Modifiers::SHIFT | Modifiers::CTRL,
+        }
+    }
+
+    pub fn bind(mut self, chord: Chord, action: Action) -> Self {
+        self.chords.insert(chord, action);
+        self
+    }
+
+    pub fn unbind(mut self, chord: Chord) -> Self {
+        self.chords.remove(&chord);
+        self
+    }
+
+    pub fn navigation(mut self, navigation: Navigation) -> Self {
+        let wheel = Chord::Wheel(Modifiers::empty());
+
+        // Trackpads pan with a plain swipe and send pinches with the command
+        // key held, so a plain wheel zoom moves over to that chord
+        if navigation == Navigation::Trackpad && self.action(wheel) == Some(Action::Zoom) {
+            self.chords.remove(&wheel);
+            self.chords
+                .insert(Chord::Wheel(Modifiers::COMMAND), Action::Zoom);
+        }
+
+        Self { navigation, ..self }
+    }
+
+    pub fn button(self, button: mouse::Button) -> Self {
+        Self { button, ..self }
+    }
+
+    pub fn toggle(self, toggle: Modifiers) -> Self {
+        Self { toggle, ..self }
+    }
+
+    pub(super) fn is_trackpad(&self) -> bool {
+        self.navigation == Navigation::Trackpad
+    }
+
+    pub(super) fn action(&self, chord: Chord) -> Option<Action> {
+        self.chords.get(&chord).copied()
+    }
+
+    pub(super) fn is_button(&self, button: mouse::Button) -> bool {
+        self.button == button
+    }
+
+    // Any of the toggle modifiers adds or removes a clicked node
+    pub(super) fn is_toggle(&self, modifiers: Modifiers) -> bool {
+        modifiers.intersects(self.toggle)
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        let none = Modifiers::empty();
+        let command = Modifiers::COMMAND;
+
+        // Zoom keys work bare or with the platform command key, as in browsers
+        let zoom_keys = [
+            (KeyCode::Minus, Action::ZoomOut),
+            (KeyCode::Equals, Action::ZoomIn),
+            (KeyCode::Key0, Action::ResetZoom),
+        ]
+        .into_iter()
+        .flat_map(|(key, action)| {
+            [
+                (Chord::Key(key, none), action),
+                (Chord::Key(key, command), action),
+            ]
+        });
+
+        Self {
+            chords: [
+                (Chord::Mouse(mouse::Button::Left, none), Action::Pan),
+                (
+                    Chord::Mouse(mouse::Button::Left, Modifiers::SHIFT),
+                    Action::Select,
+                ),
+                (Chord::Wheel(none), Action::Zoom),
+                (Chord::Key(KeyCode::F, none), Action::Fit),
+                (Chord::Key(KeyCode::Space, none), Action::ResetView),
+                (Chord::Key(KeyCode::Z, command), Action::Undo),
+                (
+                    Chord::Key(KeyCode::Z, command | Modifiers::SHIFT),
+                    Action::Redo,
+                ),
+                (Chord::Key(KeyCode::Y, command), Action::Redo),
+                (Chord::Key(KeyCode::Delete, none), Action::Delete),
+                (Chord::Key(KeyCode::Back, none), Action::Delete),
+            ]
+            .into_iter()
+            .chain(zoom_keys)
+            .collect(),
+            ..Self::new()
+        }
+    }
+}
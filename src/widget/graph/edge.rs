@@ -0,0 +1,840 @@
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+
+use iced_core::{Color, Point, Rectangle, Size, Vector};
+use iced_graphics::widget::canvas::{path, Path};
+
+use super::node::{Anchor, Side};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edge<K> {
+    pub(super) from_port: usize,
+    pub(super) to: K,
+    pub(super) to_port: usize,
+    pub(super) routing: Option<Routing>,
+    pub(super) label: Option<String>,
+    pub(super) appearance: Appearance,
+}
+
+impl<K> Edge<K> {
+    pub fn new(from_port: usize, to: K, to_port: usize) -> Self {
+        Self {
+            from_port,
+            to,
+            to_port,
+            routing: None,
+            label: None,
+            appearance: Appearance::default(),
+        }
+    }
+
+    pub fn routing(mut self, routing: Routing) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn appearance(mut self, appearance: Appearance) -> Self {
+        self.appearance = appearance;
+        self
+    }
+
+    pub fn from_port(&self) -> usize {
+        self.from_port
+    }
+
+    pub fn to(&self) -> &K {
+        &self.to
+    }
+
+    pub fn to_port(&self) -> usize {
+        self.to_port
+    }
+}
+
+impl<K: Clone> Edge<K> {
+    pub(super) fn endpoints(&self, from: K) -> (Endpoint<K>, Endpoint<K>) {
+        (
+            Endpoint::new(from, self.from_port),
+            Endpoint::new(self.to.clone(), self.to_port),
+        )
+    }
+}
+
+impl<K> From<K> for Edge<K> {
+    fn from(to: K) -> Self {
+        Self::new(0, to, 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Endpoint<K> {
+    pub node: K,
+    pub port: usize,
+}
+
+impl<K> Endpoint<K> {
+    pub fn new(node: K, port: usize) -> Self {
+        Self { node, port }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Appearance {
+    pub color: Option<Color>,
+    pub width: Option<f32>,
+    pub dash: Cow<'static, [f32]>,
+    pub flow: f32,
+    pub opacity: f32,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            color: None,
+            width: None,
+            dash: Cow::Borrowed(&[]),
+            flow: 0.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl Appearance {
+    // Floats are compared by their bits, so equality agrees with hashing
+    fn bits(&self) -> impl Iterator<Item = u32> + '_ {
+        let color = self.color.map(|Color { r, g, b, a }| [r, g, b, a]);
+
+        [color.is_some(), self.width.is_some()]
+            .map(u32::from)
+            .into_iter()
+            .chain(color.into_iter().flatten().map(f32::to_bits))
+            .chain(self.width.map(f32::to_bits))
+            .chain([self.flow.to_bits(), self.opacity.to_bits()])
+            .chain(self.dash.iter().map(|segment| segment.to_bits()))
+    }
+}
+
+impl PartialEq for Appearance {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits().eq(other.bits())
+    }
+}
+
+impl Eq for Appearance {}
+
+impl Hash for Appearance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for bits in self.bits() {
+            bits.hash(state);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Routing {
+    #[default]
+    Bezier,
+    Straight,
+    Orthogonal,
+    Step,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Marker {
+    #[default]
+    None,
+    Arrow,
+    OpenArrow,
+    Circle,
+    Diamond,
+}
+
+impl Marker {
+    fn is_filled(self) -> bool {
+        !matches!(self, Marker::None | Marker::OpenArrow)
+    }
+
+    fn draw(
+        self,
+        p: &mut path::Builder,
+        tip: Point,
+        direction: Vector,
+        size: f32,
+        transform: &impl Fn(Point) -> Point,
+    ) {
+        let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+
+        if length == 0.0 {
+            return;
+        }
+
+        // Along the curve, pointing away from it, and its normal
+        let along = direction * (1.0 / length);
+        let normal = Vector::new(-along.y, along.x);
+
+        let at =
+            |back: f32, side: f32| transform(tip - along * (size * back) + normal * (size * side));
+
+        match self {
+            Marker::None => {}
+            Marker::Arrow => {
+                p.move_to(at(0.0, 0.0));
+                p.line_to(at(1.0, 0.5));
+                p.line_to(at(1.0, -0.5));
+                p.close();
+            }
+            Marker::OpenArrow => {
+                p.move_to(at(1.0, 0.5));
+                p.line_to(at(0.0, 0.0));
+                p.line_to(at(1.0, -0.5));
+            }
+            Marker::Circle => {
+                let center = at(0.5, 0.0);
+
+                p.circle(center, center.distance(at(0.0, 0.0)));
+            }
+            Marker::Diamond => {
+                p.move_to(at(0.0, 0.0));
+                p.line_to(at(0.5, 0.35));
+                p.line_to(at(1.0, 0.0));
+                p.line_to(at(0.5, -0.35));
+                p.close();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Connector {
+    Curve {
+        start: Point,
+        control_a: Point,
+        control_b: Point,
+        end: Point,
+    },
+    Polyline {
+        points: Vec<Point>,
+        radius: f32,
+    },
+}
+
+impl Connector {
+    const SEGMENTS: usize = 32;
+    const STUB: f32 = 20.0;
+    const CORNER_RADIUS: f32 = 10.0;
+    const CORNER_SEGMENTS: usize = 8;
+
+    pub fn new(start: Anchor, end: Anchor, routing: Routing) -> Self {
+        match routing {
+            Routing::Bezier => {
+                let control = |from: Point, side: Side, to: Point| {
+                    let direction = side.direction();
+                    let distance =
+                        ((to.x - from.x) * direction.x + (to.y - from.y) * direction.y) / 2.0;
+
+                    from + direction * distance.max(30.0)
+                };
+
+                Self::Curve {
+                    start: start.position,
+                    control_a: control(start.position, start.side, end.position),
+                    control_b: control(end.position, end.side, start.position),
+                    end: end.position,
+                }
+            }
+            Routing::Straight => Self::Polyline {
+                points: vec![start.position, end.position],
+                radius: 0.0,
+            },
+            Routing::Orthogonal => {
+                // Leave and enter ports perpendicular to their side before
+                // turning, with rounded corners
+                let a = start.position + start.side.direction() * Self::STUB;
+                let b = end.position + end.side.direction() * Self::STUB;
+
+                Self::Polyline {
+                    points: polyline(
+                        [start.position, a]
+                            .into_iter()
+                            .chain(elbow(a, b, start.side))
+                            .chain([b, end.position]),
+                    ),
+                    radius: Self::CORNER_RADIUS,
+                }
+            }
+            Routing::Step => Self::Polyline {
+                points: polyline(
+                    [start.position]
+                        .into_iter()
+                        .chain(elbow(start.position, end.position, start.side))
+                        .chain([end.position]),
+                ),
+                radius: 0.0,
+            },
+        }
+    }
+
+    fn flattened(&self) -> Vec<Point> {
+        match self {
+            Self::Curve {
+                start,
+                control_a,
+                control_b,
+                end,
+            } => {
+                let points = [*start, *control_a, *control_b, *end];
+
+                (0..=Self::SEGMENTS)
+                    .map(|i| bezier_point(points, i as f32 / Self::SEGMENTS as f32))
+                    .collect()
+            }
+            Self::Polyline { points, radius } => {
+                let mut flattened = vec![points[0]];
+
+                for window in points.windows(3) {
+                    let (a, corner, b) = (window[0], window[1], window[2]);
+
+                    match corner_arc(a, corner, b, *radius) {
+                        Some((from, to)) => {
+                            flattened.extend((0..=Self::CORNER_SEGMENTS).map(|i| {
+                                quadratic_point(
+                                    [from, corner, to],
+                                    i as f32 / Self::CORNER_SEGMENTS as f32,
+                                )
+                            }))
+                        }
+                        None => flattened.push(corner),
+                    }
+                }
+
+                flattened.push(points[points.len() - 1]);
+                flattened
+            }
+        }
+    }
+
+    fn segments(&self) -> Vec<(Point, Point)> {
+        self.flattened()
+            .windows(2)
+            .map(|segment| (segment[0], segment[1]))
+            .collect()
+    }
+
+    fn points(&self) -> Vec<Point> {
+        match self {
+            Self::Curve {
+                start,
+                control_a,
+                control_b,
+                end,
+            } => vec![*start, *control_a, *control_b, *end],
+            Self::Polyline { points, .. } => points.clone(),
+        }
+    }
+
+    fn ends(&self) -> [(Point, Vector); 2] {
+        match self {
+            Self::Curve {
+                start,
+                control_a,
+                control_b,
+                end,
+            } => {
+                let direction = |tip: Point, control: Point, other: Point| {
+                    if tip == control {
+                        tip - other
+                    } else {
+                        tip - control
+                    }
+                };
+
+                [
+                    (*start, direction(*start, *control_a, *end)),
+                    (*end, direction(*end, *control_b, *start)),
+                ]
+            }
+            Self::Polyline { points, .. } => {
+                let first = points[0];
+                let last = points[points.len() - 1];
+
+                [
+                    (first, first - *points.get(1).unwrap_or(&first)),
+                    (last, last - points[points.len().saturating_sub(2)]),
+                ]
+            }
+        }
+    }
+
+    pub fn markers(
+        &self,
+        source: Marker,
+        target: Marker,
+        size: f32,
+        transform: impl Fn(Point) -> Point,
+    ) -> (Path, Path) {
+        let [start, end] = self.ends();
+        let ends = [(source, start), (target, end)];
+
+        let draw = |filled: bool| {
+            Path::new(|p| {
+                for (marker, (tip, direction)) in ends {
+                    if marker.is_filled() == filled {
+                        marker.draw(p, tip, direction, size, &transform);
+                    }
+                }
+            })
+        };
+
+        (draw(true), draw(false))
+    }
+
+    pub fn midpoint(&self) -> Point {
+        let segments = self.segments();
+
+        let length: f32 = segments.iter().map(|(a, b)| a.distance(*b)).sum();
+        let mut remaining = length / 2.0;
+
+        for (a, b) in segments {
+            let distance = a.distance(b);
+
+            if remaining <= distance && distance > 0.0 {
+                return a + (b - a) * (remaining / distance);
+            }
+
+            remaining -= distance;
+        }
+
+        self.ends()[0].0
+    }
+
+    pub fn distance(&self, point: Point) -> f32 {
+        self.segments()
+            .into_iter()
+            .map(|(a, b)| segment_distance(point, a, b))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        // A bezier curve always lies within the hull of its control points
+        let (min, max) = self.points().iter().fold(
+            (
+                Point::new(f32::INFINITY, f32::INFINITY),
+                Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+            ),
+            |(min, max), point| {
+                (
+                    Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    Point::new(max.x.max(point.x), max.y.max(point.y)),
+                )
+            },
+        );
+
+        Rectangle::new(min, Size::new(max.x - min.x, max.y - min.y))
+    }
+
+    pub fn path(&self, transform: impl Fn(Point) -> Point) -> Path {
+        Path::new(|p| match self {
+            Self::Curve {
+                start,
+                control_a,
+                control_b,
+                end,
+            } => {
+                p.move_to(transform(*start));
+                p.bezier_curve_to(
+                    transform(*control_a),
+                    transform(*control_b),
+                    transform(*end),
+                );
+            }
+            Self::Polyline { points, radius } => {
+                p.move_to(transform(points[0]));
+
+                for window in points.windows(3) {
+                    let (a, corner, b) = (window[0], window[1], window[2]);
+
+                    match corner_arc(a, corner, b, *radius) {
+                        Some((from, to)) => {
+                            p.line_to(transform(from));
+                            p.quadratic_curve_to(transform(corner), transform(to));
+                        }
+                        None => p.line_to(transform(corner)),
+                    }
+                }
+
+                p.line_to(transform(points[points.len() - 1]));
+            }
+        })
+    }
+
+    pub fn dashed(&self, dash: &[f32], offset: f32, transform: impl Fn(Point) -> Point) -> Path {
+        match self.dashes(dash, offset) {
+            Some(dashes) => Path::new(|p| {
+                for dash in dashes {
+                    p.move_to(transform(dash[0]));
+
+                    for point in &dash[1..] {
+                        p.line_to(transform(*point));
+                    }
+                }
+            }),
+            None => self.path(transform),
+        }
+    }
+
+    fn dashes(&self, dash: &[f32], offset: f32) -> Option<Vec<Vec<Point>>> {
+        // An odd pattern alternates dashes and gaps over two repetitions
+        let pattern = if dash.len() % 2 == 1 {
+            dash.repeat(2)
+        } else {
+            dash.to_vec()
+        };
+
+        let period: f32 = pattern.iter().sum();
+
+        if period <= 0.0 {
+            return None;
+        }
+
+        // Find where the pattern stands at the start of the connector, so a
+        // growing offset moves the dashes toward the target
+        let mut position = (-offset).rem_euclid(period);
+        let mut index = 0;
+
+        while position >= pattern[index] {
+            position -= pattern[index];
+            index = (index + 1) % pattern.len();
+        }
+
+        let mut remaining = pattern[index] - position;
+
+        let points = self.flattened();
+        let mut drawing = index % 2 == 0;
+        let mut dashes = vec![];
+        let mut current = if drawing { vec![points[0]] } else { vec![] };
+
+        for segment in points.windows(2) {
+            let (mut a, b) = (segment[0], segment[1]);
+            let mut length = a.distance(b);
+
+            while remaining < length {
+                let split = a + (b - a) * (remaining / length);
+
+                if drawing {
+                    current.push(split);
+                    dashes.push(std::mem::take(&mut current));
+                } else {
+                    current = vec![split];
+                }
+
+                drawing = !drawing;
+                length -= remaining;
+                a = split;
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+            }
+
+            remaining -= length;
+
+            if drawing {
+                current.push(b);
+            }
+        }
+
+        if current.len() > 1 {
+            dashes.push(current);
+        }
+
+        Some(dashes)
+    }
+}
+
+fn bezier_point(points: [Point; 4], t: f32) -> Point {
+    let u = 1.0 - t;
+
+    let weights = [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t];
+
+    let (x, y) = weights
+        .iter()
+        .zip(points)
+        .fold((0.0, 0.0), |(x, y), (weight, point)| {
+            (x + weight * point.x, y + weight * point.y)
+        });
+
+    Point::new(x, y)
+}
+
+fn quadratic_point(points: [Point; 3], t: f32) -> Point {
+    let u = 1.0 - t;
+
+    let weights = [u * u, 2.0 * u * t, t * t];
+
+    let (x, y) = weights
+        .iter()
+        .zip(points)
+        .fold((0.0, 0.0), |(x, y), (weight, point)| {
+            (x + weight * point.x, y + weight * point.y)
+        });
+
+    Point::new(x, y)
+}
+
+// Where a rounded corner leaves each of its two legs, if it is rounded at all
+fn corner_arc(a: Point, corner: Point, b: Point, radius: f32) -> Option<(Point, Point)> {
+    let radius = radius
+        .min(a.distance(corner) / 2.0)
+        .min(b.distance(corner) / 2.0);
+
+    (radius > 0.0).then(|| {
+        let toward = |to: Point| corner + (to - corner) * (radius / to.distance(corner));
+
+        (toward(a), toward(b))
+    })
+}
+
+fn elbow(from: Point, to: Point, side: Side) -> [Point; 2] {
+    let direction = side.direction();
+    let is_behind = (to.x - from.x) * direction.x + (to.y - from.y) * direction.y < 0.0;
+
+    // When the target lies behind the source, turning halfway along the main
+    // axis would double back over the stubs, so split along the cross axis
+    // and go around instead
+    if matches!(side, Side::Left | Side::Right) != is_behind {
+        let x = (from.x + to.x) / 2.0;
+
+        [Point::new(x, from.y), Point::new(x, to.y)]
+    } else {
+        let y = (from.y + to.y) / 2.0;
+
+        [Point::new(from.x, y), Point::new(to.x, y)]
+    }
+}
+
+fn polyline(points: impl IntoIterator<Item = Point>) -> Vec<Point> {
+    let mut polyline: Vec<Point> = vec![];
+
+    for point in points {
+        if polyline.last() != Some(&point) {
+            polyline.push(point);
+        }
+    }
+
+    polyline
+}
+
+fn segment_distance(point: Point, a: Point, b: Point) -> f32 {
+    let ab = b - a;
+    let length_squared = ab.x * ab.x + ab.y * ab.y;
+
+    if length_squared == 0.0 {
+        return point.distance(a);
+    }
+
+    let ap = point - a;
+    let t = ((ap.x * ab.x + ap.y * ab.y) / length_squared).clamp(0.0, 1.0);
+
+    point.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_through(points: &[(f32, f32)]) -> Connector {
+        Connector::Polyline {
+            points: points.iter().map(|(x, y)| Point::new(*x, *y)).collect(),
+            radius: 0.0,
+        }
+    }
+
+    fn dashes(connector: &Connector, dash: &[f32], offset: f32) -> Vec<Vec<(f32, f32)>> {
+        connector
+            .dashes(dash, offset)
+            .unwrap()
+            .into_iter()
+            .map(|dash| dash.into_iter().map(|point| (point.x, point.y)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn dashes_alternate_with_gaps() {
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0)]);
+
+        assert_eq!(
+            dashes(&line, &[2.0, 2.0], 0.0),
+            vec![
+                vec![(0.0, 0.0), (2.0, 0.0)],
+                vec![(4.0, 0.0), (6.0, 0.0)],
+                vec![(8.0, 0.0), (10.0, 0.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn offset_moves_dashes_toward_the_target() {
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0)]);
+
+        assert_eq!(
+            dashes(&line, &[2.0, 2.0], 1.0),
+            vec![
+                vec![(1.0, 0.0), (3.0, 0.0)],
+                vec![(5.0, 0.0), (7.0, 0.0)],
+                vec![(9.0, 0.0), (10.0, 0.0)],
+            ]
+        );
+
+        // A whole period later the pattern is back where it started
+        assert_eq!(
+            dashes(&line, &[2.0, 2.0], 5.0),
+            dashes(&line, &[2.0, 2.0], 1.0)
+        );
+    }
+
+    #[test]
+    fn odd_patterns_repeat_with_swapped_roles() {
+        let line = line_through(&[(0.0, 0.0), (9.0, 0.0)]);
+
+        assert_eq!(
+            dashes(&line, &[3.0], 0.0),
+            vec![vec![(0.0, 0.0), (3.0, 0.0)], vec![(6.0, 0.0), (9.0, 0.0)]]
+        );
+    }
+
+    #[test]
+    fn odd_patterns_wrap_around_with_an_offset() {
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0)]);
+
+        // [3, 1, 2] runs as dash 3, gap 1, dash 2, gap 3, dash 1, gap 2, and
+        // an offset of 4 starts one unit before the end of the gap of 3
+        assert_eq!(
+            dashes(&line, &[3.0, 1.0, 2.0], 4.0),
+            vec![
+                vec![(1.0, 0.0), (2.0, 0.0)],
+                vec![(4.0, 0.0), (7.0, 0.0)],
+                vec![(8.0, 0.0), (10.0, 0.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn dashes_follow_corners() {
+        let line = line_through(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)]);
+
+        assert_eq!(
+            dashes(&line, &[6.0, 2.0], 0.0),
+            vec![vec![(0.0, 0.0), (4.0, 0.0), (4.0, 2.0)]]
+        );
+    }
+
+    #[test]
+    fn empty_patterns_are_solid() {
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0)]);
+
+        assert!(line.dashes(&[], 0.0).is_none());
+        assert!(line.dashes(&[0.0, 0.0], 0.0).is_none());
+    }
+
+    #[test]
+    fn elbow_turns_halfway_toward_the_target() {
+        assert_eq!(
+            elbow(Point::new(0.0, 0.0), Point::new(10.0, 20.0), Side::Right),
+            [Point::new(5.0, 0.0), Point::new(5.0, 20.0)]
+        );
+        assert_eq!(
+            elbow(Point::new(0.0, 0.0), Point::new(10.0, 20.0), Side::Bottom),
+            [Point::new(0.0, 10.0), Point::new(10.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn elbow_goes_around_targets_behind_the_source() {
+        assert_eq!(
+            elbow(Point::new(0.0, 0.0), Point::new(-10.0, 20.0), Side::Right),
+            [Point::new(0.0, 10.0), Point::new(-10.0, 10.0)]
+        );
+        assert_eq!(
+            elbow(Point::new(0.0, 0.0), Point::new(10.0, -20.0), Side::Bottom),
+            [Point::new(5.0, 0.0), Point::new(5.0, -20.0)]
+        );
+    }
+
+    #[test]
+    fn orthogonal_routes_never_double_back() {
+        let stub = Connector::STUB;
+        let connector = Connector::new(
+            Anchor {
+                position: Point::new(0.0, 0.0),
+                side: Side::Right,
+            },
+            Anchor {
+                position: Point::new(-50.0, 40.0),
+                side: Side::Left,
+            },
+            Routing::Orthogonal,
+        );
+
+        assert_eq!(
+            connector.points(),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(stub, 0.0),
+                Point::new(stub, 20.0),
+                Point::new(-50.0 - stub, 20.0),
+                Point::new(-50.0 - stub, 40.0),
+                Point::new(-50.0, 40.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn corners_are_cut_by_the_radius() {
+        assert_eq!(
+            corner_arc(
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+                4.0
+            ),
+            Some((Point::new(6.0, 0.0), Point::new(10.0, 4.0)))
+        );
+
+        // The radius never takes more than half of either side
+        assert_eq!(
+            corner_arc(
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 2.0),
+                4.0
+            ),
+            Some((Point::new(9.0, 0.0), Point::new(10.0, 1.0)))
+        );
+
+        assert_eq!(
+            corner_arc(
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+                0.0
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn midpoint_is_halfway_along_the_connector() {
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(line.midpoint(), Point::new(10.0, 0.0));
+
+        let line = line_through(&[(0.0, 0.0), (10.0, 0.0), (10.0, 30.0)]);
+
+        assert_eq!(line.midpoint(), Point::new(10.0, 10.0));
+    }
+}
@@ -1,68 +1,192 @@
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
 use iced_core::{Background, Color, Length, Point, Rectangle, Size, Vector};
+use iced_graphics::widget::canvas::Cache;
 use iced_graphics::{Renderer, Transformation};
-use iced_native::widget::{tree, Tree};
+use iced_native::text::{self, Renderer as _};
+use iced_native::widget::{tree, Operation, Tree};
 use iced_native::{
-    event, keyboard, layout, mouse, renderer, Element, Layout, Renderer as _, Widget,
+    alignment, event, keyboard, layout, mouse, overlay, renderer, Element, Layout, Renderer as _,
+    Widget,
 };
 
-use super::{node, Node};
+use super::bindings::{Action, Chord};
+use super::edge::{Connector, Endpoint, Marker, Routing};
+use super::minimap::Minimap;
+use super::node::{Anchor, PortKind};
+use super::{node, Bindings, Edge, Node};
 
-#[derive(Debug, Clone, Copy)]
-pub enum Event {
-    NodeMoved { index: usize, offset: Vector },
+#[derive(Debug, Clone)]
+pub enum Event<K> {
+    NodesMoved(Vec<(K, Vector)>),
     Scaled(f32, Vector),
     Translated(Vector),
+    EdgeCreated { from: Endpoint<K>, to: Endpoint<K> },
+    EdgeSelected { from: Endpoint<K>, to: Endpoint<K> },
+    EdgeDeleteRequested { from: Endpoint<K>, to: Endpoint<K> },
+    EdgeLabelClicked { from: Endpoint<K>, to: Endpoint<K> },
+    SelectionChanged(Vec<K>),
+    Undo,
+    Redo,
+}
+
+#[derive(Debug)]
+struct State<K> {
+    keys: Vec<K>,
+    interaction: Interaction<K>,
+    hovered_edge: Option<(Endpoint<K>, Endpoint<K>)>,
+    selected_edge: Option<(Endpoint<K>, Endpoint<K>)>,
+    selection: Vec<K>,
+    is_selection_pruned: bool,
+    wheel_lines: f32,
+    modifiers: keyboard::Modifiers,
+    is_focused: bool,
+    connectors: Cache,
+    connectors_key: Cell<u64>,
+    connectors_region: Cell<Option<Rectangle>>,
+    grid: Cache,
+    grid_key: Cell<u64>,
+}
+
+impl<K> State<K> {
+    fn new(keys: Vec<K>) -> Self {
+        Self {
+            keys,
+            interaction: Interaction::Idle,
+            hovered_edge: None,
+            selected_edge: None,
+            selection: vec![],
+            is_selection_pruned: false,
+            wheel_lines: 0.0,
+            modifiers: keyboard::Modifiers::default(),
+            is_focused: false,
+            connectors: Cache::new(),
+            connectors_key: Cell::new(0),
+            connectors_region: Cell::new(None),
+            grid: Cache::new(),
+            grid_key: Cell::new(0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
-enum Interaction {
-    #[default]
+impl<K: PartialEq> State<K> {
+    fn node_offset(&self, key: &K) -> Vector {
+        match self.interaction {
+            Interaction::Moving { offset, .. } if self.selection.contains(key) => offset,
+            _ => Vector::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Interaction<K> {
     Idle,
     Translating {
+        button: mouse::Button,
         started_at: Point,
         offset: Vector,
     },
+    Connecting {
+        from: Endpoint<K>,
+        cursor: Point,
+    },
+    Selecting {
+        button: mouse::Button,
+        started_at: Point,
+        cursor: Point,
+    },
+    Moving {
+        grabbed: K,
+        started_at: Point,
+        offset: Vector,
+    },
+    Navigating {
+        offset: Vector,
+    },
 }
 
-impl Interaction {
+impl<K> Interaction<K> {
     fn offset(&self) -> Vector {
         match self {
-            Interaction::Idle => Vector::default(),
-            Interaction::Translating { offset, .. } => *offset,
+            Interaction::Translating { offset, .. } | Interaction::Navigating { offset } => *offset,
+            _ => Vector::default(),
         }
     }
 }
 
-pub struct Editor<'a, Message, Renderer>
+pub struct Editor<'a, K, Message, Renderer>
 where
     Renderer: iced_native::Renderer,
     Renderer::Theme: StyleSheet + node::StyleSheet,
 {
-    nodes: Vec<Node<'a, Message, Renderer>>,
+    nodes: Vec<Node<'a, K, Message, Renderer>>,
+    indices: HashMap<K, usize>,
     scaling: f32,
     translation: Vector,
     max_node_size: Size,
-    on_event: Box<dyn Fn(Event) -> Message + 'a>,
+    fit_padding: f32,
+    min_scaling: f32,
+    max_scaling: f32,
+    wheel_sensitivity: f32,
+    grid_snap: Option<f32>,
+    minimap: Option<Size>,
+    routing: Routing,
+    label_size: u16,
+    label_min_scaling: f32,
+    zoom_step: f32,
+    zoom_levels: Vec<f32>,
+    time: Duration,
+    bindings: Bindings,
+    on_event: Box<dyn Fn(Event<K>) -> Message + 'a>,
     style: <Renderer::Theme as StyleSheet>::Style,
 }
 
-impl<'a, Message, Renderer> Editor<'a, Message, Renderer>
+impl<'a, K, Message, Renderer> Editor<'a, K, Message, Renderer>
 where
+    K: Hash + Eq + Clone,
     Renderer: iced_native::Renderer,
     Renderer::Theme: StyleSheet + node::StyleSheet,
 {
-    const MIN_SCALING: f32 = 0.1;
-    const MAX_SCALING: f32 = 5.0;
+    const PIXELS_PER_LINE: f32 = 20.0;
+    const MIN_GRID_SPACING: f32 = 8.0;
+    const PORT_HIT_RADIUS: f32 = 8.0;
+    const EDGE_HIT_DISTANCE: f32 = 5.0;
 
     pub fn new(
-        nodes: Vec<Node<'a, Message, Renderer>>,
-        on_event: impl Fn(Event) -> Message + 'a,
+        nodes: Vec<Node<'a, K, Message, Renderer>>,
+        on_event: impl Fn(Event<K>) -> Message + 'a,
     ) -> Self {
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.key().clone(), index))
+            .collect::<HashMap<_, _>>();
+
+        debug_assert_eq!(indices.len(), nodes.len(), "node keys must be unique");
+
         Self {
             nodes,
+            indices,
             scaling: 1.0,
             translation: Vector::new(0.0, 0.0),
             max_node_size: Size::new(300.0, 300.0),
+            fit_padding: 20.0,
+            min_scaling: 0.1,
+            max_scaling: 5.0,
+            wheel_sensitivity: 1.0 / 15.0,
+            grid_snap: None,
+            minimap: None,
+            routing: Routing::default(),
+            label_size: 14,
+            label_min_scaling: 0.5,
+            zoom_step: 1.0 / 15.0,
+            zoom_levels: vec![],
+            time: Duration::ZERO,
+            bindings: Bindings::default(),
             on_event: Box::new(on_event),
             style: Default::default(),
         }
@@ -86,61 +210,958 @@ where
         }
     }
 
-    fn transformation(&self) -> glam::Mat4 {
+    pub fn fit_padding(self, fit_padding: f32) -> Self {
+        Self {
+            fit_padding,
+            ..self
+        }
+    }
+
+    pub fn min_scaling(self, min_scaling: f32) -> Self {
+        debug_assert!(min_scaling > 0.0, "scaling limits must be positive");
+
+        // Whichever limit is set last wins when they cross
+        Self {
+            min_scaling,
+            max_scaling: self.max_scaling.max(min_scaling),
+            ..self
+        }
+        .clamp_zoom_levels()
+    }
+
+    pub fn max_scaling(self, max_scaling: f32) -> Self {
+        debug_assert!(max_scaling > 0.0, "scaling limits must be positive");
+
+        Self {
+            max_scaling,
+            min_scaling: self.min_scaling.min(max_scaling),
+            ..self
+        }
+        .clamp_zoom_levels()
+    }
+
+    pub fn wheel_sensitivity(self, wheel_sensitivity: f32) -> Self {
+        Self {
+            wheel_sensitivity,
+            ..self
+        }
+    }
+
+    pub fn zoom_step(self, zoom_step: f32) -> Self {
+        Self { zoom_step, ..self }
+    }
+
+    pub fn zoom_levels(self, zoom_levels: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            zoom_levels: zoom_levels.into_iter().collect(),
+            ..self
+        }
+        .clamp_zoom_levels()
+    }
+
+    fn clamp_zoom_levels(mut self) -> Self {
+        let (min, max) = (self.min_scaling, self.max_scaling);
+
+        self.zoom_levels = self
+            .zoom_levels
+            .into_iter()
+            .filter(|level| level.is_finite())
+            .map(|level| level.max(min).min(max))
+            .collect();
+
+        self.zoom_levels.sort_by(f32::total_cmp);
+        self.zoom_levels.dedup();
+
+        self
+    }
+
+    pub fn snap_to_grid(self, spacing: f32) -> Self {
+        Self {
+            grid_snap: Some(spacing),
+            ..self
+        }
+    }
+
+    pub fn routing(self, routing: Routing) -> Self {
+        Self { routing, ..self }
+    }
+
+    pub fn label_size(self, label_size: u16) -> Self {
+        Self { label_size, ..self }
+    }
+
+    pub fn hide_labels_below(self, scaling: f32) -> Self {
+        Self {
+            label_min_scaling: scaling,
+            ..self
+        }
+    }
+
+    pub fn bindings(self, bindings: Bindings) -> Self {
+        Self { bindings, ..self }
+    }
+
+    pub fn time(self, time: Duration) -> Self {
+        Self { time, ..self }
+    }
+
+    pub fn minimap(self, size: Size) -> Self {
+        Self {
+            minimap: Some(size),
+            ..self
+        }
+    }
+
+    // The public translation is relative to the editor's origin, while node
+    // layouts are absolute, so the origin is folded in before transforming
+    fn absolute(&self, bounds: Rectangle, translation: Vector) -> Vector {
+        let origin = Vector::new(bounds.x, bounds.y);
+
+        translation - origin + origin * (1.0 / self.scaling)
+    }
+
+    fn transformation(&self, bounds: Rectangle) -> glam::Mat4 {
+        let translation = self.absolute(bounds, self.translation);
+
         (Transformation::identity()
             * Transformation::scale(self.scaling, self.scaling)
-            * Transformation::translate(self.translation.x, self.translation.y))
+            * Transformation::translate(translation.x, translation.y))
         .into()
     }
 
-    fn transform_cursor(&self, cursor_position: Point) -> Point {
+    fn transform_cursor(&self, bounds: Rectangle, cursor_position: Point) -> Point {
         let Point { x, y } = cursor_position;
 
         let glam::Vec3 { x, y, .. } = self
-            .transformation()
+            .transformation(bounds)
             .inverse()
             .transform_point3(glam::Vec3::new(x, y, 1.0));
 
         Point::new(x, y)
     }
 
-    fn zoom(&mut self, y: f32, position: Point) {
-        let old_scaling = self.scaling;
+    fn project(&self, bounds: Rectangle, point: Point) -> Point {
+        let translation = self.absolute(bounds, self.translation);
 
-        self.scaling = (self.scaling * (1.0 + y / 15.0))
-            .max(Self::MIN_SCALING)
-            .min(Self::MAX_SCALING);
+        Point::new(
+            (point.x + translation.x) * self.scaling,
+            (point.y + translation.y) * self.scaling,
+        )
+    }
 
-        let change = self.scaling / old_scaling - 1.0;
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
 
-        self.translation =
-            self.translation - Vector::new(position.x, position.y) * change * (1.0 / self.scaling);
+    fn node_bounds(&self, state: &State<K>, layout: Layout<'_>, index: usize) -> Rectangle {
+        layout.children().nth(index).unwrap().bounds() + state.node_offset(self.nodes[index].key())
     }
 
-    fn reset_zoom(&mut self, position: Point) {
-        let old_scaling = self.scaling;
+    fn all_node_bounds(&self, state: &State<K>, layout: Layout<'_>) -> Vec<Rectangle> {
+        self.nodes
+            .iter()
+            .zip(layout.children())
+            .map(|(node, layout)| layout.bounds() + state.node_offset(node.key()))
+            .collect()
+    }
+
+    fn edge_anchors(
+        &self,
+        bounds: &[Rectangle],
+        from: usize,
+        edge: &Edge<K>,
+    ) -> Option<(Anchor, Anchor)> {
+        let to = self.index_of(&edge.to)?;
+
+        let start = self.nodes[from].anchor(bounds[from], PortKind::Output, edge.from_port)?;
+        let end = self.nodes[to].anchor(bounds[to], PortKind::Input, edge.to_port)?;
+
+        Some((start, end))
+    }
+
+    fn port_at(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        position: Point,
+        kind: PortKind,
+    ) -> Option<Endpoint<K>> {
+        let bounds = self.all_node_bounds(state, layout);
+
+        self.nodes
+            .iter()
+            .zip(bounds)
+            .rev()
+            .find_map(|(node, bounds)| {
+                (0..node.ports(kind).len()).find_map(|port| {
+                    let anchor = node.anchor(bounds, kind, port)?;
+
+                    (anchor.position.distance(position) <= Self::PORT_HIT_RADIUS)
+                        .then(|| Endpoint::new(node.key().clone(), port))
+                })
+            })
+    }
+
+    fn node_at(&self, state: &State<K>, layout: Layout<'_>, position: Point) -> Option<usize> {
+        self.all_node_bounds(state, layout)
+            .iter()
+            .rposition(|bounds| bounds.contains(position))
+    }
+
+    fn hovered_node(&self, children: &[Tree]) -> Option<usize> {
+        children.iter().rposition(|child| {
+            matches!(
+                child.state.downcast_ref::<node::State>(),
+                node::State::Hovered
+            )
+        })
+    }
+
+    fn select(
+        &self,
+        current: &mut Vec<K>,
+        selection: Vec<K>,
+        shell: &mut iced_native::Shell<'_, Message>,
+    ) {
+        if *current != selection {
+            *current = selection.clone();
+
+            shell.publish((self.on_event)(Event::SelectionChanged(selection)));
+        }
+    }
+
+    fn connection_target(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        position: Point,
+    ) -> Option<Endpoint<K>> {
+        if let Some(endpoint) = self.port_at(state, layout, position, PortKind::Input) {
+            return Some(endpoint);
+        }
+
+        // Dropping onto a node body connects to its closest input
+        let index = self.node_at(state, layout, position)?;
+        let node = &self.nodes[index];
+        let bounds = self.node_bounds(state, layout, index);
+
+        (0..node.ports(PortKind::Input).len())
+            .filter_map(|port| {
+                let anchor = node.anchor(bounds, PortKind::Input, port)?;
+
+                Some((port, anchor.position.distance(position)))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(port, _)| Endpoint::new(node.key().clone(), port))
+    }
+
+    fn edge_at(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        position: Point,
+    ) -> Option<(Endpoint<K>, Endpoint<K>)> {
+        // Hit distance is constant in screen space, regardless of zoom
+        let threshold = Self::EDGE_HIT_DISTANCE / self.scaling;
+        let bounds = self.all_node_bounds(state, layout);
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(from, node)| node.edges.iter().map(move |edge| (from, edge)))
+            .filter_map(|(from, edge)| {
+                let (start, end) = self.edge_anchors(&bounds, from, edge)?;
+                let connector = Connector::new(start, end, edge.routing.unwrap_or(self.routing));
+
+                // Cheap rejection before sampling the curve
+                if !expand(connector.bounds(), threshold).contains(position) {
+                    return None;
+                }
+
+                let distance = connector.distance(position);
+
+                (distance <= threshold)
+                    .then(|| (edge.endpoints(self.nodes[from].key().clone()), distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(endpoints, _)| endpoints)
+    }
+
+    fn snap(&self, origin: Vector, offset: Vector) -> Vector {
+        match self.grid_snap {
+            Some(spacing) if spacing > 0.0 => {
+                let snap = |value: f32| (value / spacing).round() * spacing;
+                let target = origin + offset;
+
+                Vector::new(snap(target.x), snap(target.y)) - origin
+            }
+            _ => offset,
+        }
+    }
+
+    fn viewport(&self, bounds: Rectangle, translation: Vector) -> Rectangle {
+        Rectangle {
+            x: bounds.x - translation.x,
+            y: bounds.y - translation.y,
+            width: bounds.width / self.scaling,
+            height: bounds.height / self.scaling,
+        }
+    }
+
+    fn is_visible(
+        &self,
+        state: &State<K>,
+        node: usize,
+        bounds: Rectangle,
+        visible: &Rectangle,
+    ) -> bool {
+        (bounds + state.node_offset(self.nodes[node].key()))
+            .intersection(visible)
+            .is_some()
+    }
+
+    fn minimap_at(&self, state: &State<K>, layout: Layout<'_>) -> Option<Minimap> {
+        let size = self.minimap?;
+        let bounds = layout.bounds();
+
+        // The committed viewport is part of the overview, so it never leaves
+        // the minimap while the user navigates
+        let world = self
+            .all_node_bounds(state, layout)
+            .into_iter()
+            .fold(self.viewport(bounds, self.translation), union);
 
-        self.scaling = 1.0;
+        Some(Minimap::new(bounds, size, world))
+    }
+
+    fn navigate(&self, bounds: Rectangle, target: Point) -> Vector {
+        Vector::new(
+            bounds.width / 2.0 / self.scaling - (target.x - bounds.x),
+            bounds.height / 2.0 / self.scaling - (target.y - bounds.y),
+        ) - self.translation
+    }
+
+    fn line<'b>(
+        &self,
+        edge: &'b Edge<K>,
+        highlight: Option<Color>,
+        appearance: &Appearance,
+    ) -> Line<'b> {
+        let overrides = &edge.appearance;
+
+        // Highlights win over the edge's own color
+        let color = highlight
+            .or(overrides.color)
+            .unwrap_or(appearance.connector_color);
+
+        Line {
+            color: Color {
+                a: color.a * overrides.opacity,
+                ..color
+            },
+            width: overrides.width.unwrap_or(appearance.connector_width),
+            dash: &overrides.dash,
+            offset: overrides.flow * self.time.as_secs_f32(),
+        }
+    }
+
+    fn highlight(
+        &self,
+        state: &State<K>,
+        endpoints: &(Endpoint<K>, Endpoint<K>),
+        appearance: &Appearance,
+    ) -> Option<Color> {
+        if state.selected_edge.as_ref() == Some(endpoints) {
+            Some(appearance.selected_connector_color)
+        } else if state.hovered_edge.as_ref() == Some(endpoints) {
+            Some(appearance.hovered_connector_color)
+        } else {
+            None
+        }
+    }
 
-        let change = self.scaling / old_scaling - 1.0;
+    // Edges attached to dragged nodes and flowing edges change every frame,
+    // so they are kept out of the cache
+    fn is_live(&self, state: &State<K>, from: &K, edge: &Edge<K>) -> bool {
+        let is_moving = matches!(state.interaction, Interaction::Moving { .. });
 
-        self.translation =
-            self.translation - Vector::new(position.x, position.y) * change * (1.0 / self.scaling);
+        (is_moving && (state.selection.contains(from) || state.selection.contains(&edge.to)))
+            || (edge.appearance.flow != 0.0 && !edge.appearance.dash.is_empty())
+    }
+
+    fn connectors(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        appearance: &Appearance,
+        region: Rectangle,
+        live: bool,
+    ) -> Vec<(Connector, Line<'_>)> {
+        let bounds = self.all_node_bounds(state, layout);
+
+        let mut connectors = vec![];
+
+        for (from_index, from) in self.nodes.iter().enumerate() {
+            for edge in &from.edges {
+                let endpoints = edge.endpoints(from.key().clone());
+                let highlight = self.highlight(state, &endpoints, appearance);
+                let is_live = self.is_live(state, from.key(), edge);
+
+                // Highlighted edges stay in the cache and are redrawn live on
+                // top, so hovering never invalidates it
+                let (included, highlight) = if live {
+                    (is_live || highlight.is_some(), highlight)
+                } else {
+                    (!is_live, None)
+                };
+
+                if !included {
+                    continue;
+                }
+
+                if let Some((start, end)) = self.edge_anchors(&bounds, from_index, edge) {
+                    let line = self.line(edge, highlight, appearance);
+                    let connector =
+                        Connector::new(start, end, edge.routing.unwrap_or(self.routing));
+
+                    // Stroke width keeps straight, axis-aligned curves from
+                    // having an empty bounding box
+                    if expand(connector.bounds(), line.reach(appearance))
+                        .intersection(&region)
+                        .is_some()
+                    {
+                        connectors.push((connector, line));
+                    }
+                }
+            }
+        }
+
+        connectors
+    }
+
+    // Only what shapes the cached edges goes into the key, so it is cheap to
+    // compute without building any connector
+    fn connectors_key(&self, state: &State<K>, layout: Layout<'_>, appearance: &Appearance) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        self.scaling.to_bits().hash(&mut hasher);
+        self.routing.hash(&mut hasher);
+        appearance.marker_size.to_bits().hash(&mut hasher);
+        appearance.source_marker.hash(&mut hasher);
+        appearance.target_marker.hash(&mut hasher);
+
+        // Dragged edges leave the cache for the duration of the drag
+        if matches!(state.interaction, Interaction::Moving { .. }) {
+            state.selection.hash(&mut hasher);
+        }
+
+        for (node, layout) in self.nodes.iter().zip(layout.children()) {
+            let bounds = layout.bounds();
+
+            [bounds.x, bounds.y, bounds.width, bounds.height]
+                .map(f32::to_bits)
+                .hash(&mut hasher);
+
+            for kind in [PortKind::Input, PortKind::Output] {
+                for port in node.ports(kind) {
+                    std::mem::discriminant(&port.side()).hash(&mut hasher);
+                }
+            }
+
+            // Live edges are drawn every frame anyway, and a flowing one would
+            // change the key as time advances
+            for edge in node
+                .edges
+                .iter()
+                .filter(|edge| !self.is_live(state, node.key(), edge))
+            {
+                edge.from_port.hash(&mut hasher);
+                edge.to.hash(&mut hasher);
+                edge.to_port.hash(&mut hasher);
+                edge.routing.hash(&mut hasher);
+                self.line(edge, None, appearance).hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    fn cached_connectors(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        visible: Rectangle,
+        appearance: &Appearance,
+    ) -> (Vector, iced_graphics::widget::canvas::Geometry) {
+        let key = self.connectors_key(state, layout, appearance);
+
+        // The geometry covers a margin around the viewport in unpanned, scaled
+        // coordinates, so short pans reuse it as is
+        let region = match state.connectors_region.get() {
+            Some(region) if state.connectors_key.get() == key && contains(region, visible) => {
+                region
+            }
+            _ => {
+                let region = Rectangle {
+                    x: visible.x - visible.width,
+                    y: visible.y - visible.height,
+                    width: visible.width * 3.0,
+                    height: visible.height * 3.0,
+                };
+
+                state.connectors.clear();
+                state.connectors_key.set(key);
+                state.connectors_region.set(Some(region));
+
+                region
+            }
+        };
+
+        let origin = Vector::new(region.x * self.scaling, region.y * self.scaling);
+        let size = Size::new(region.width * self.scaling, region.height * self.scaling);
+
+        let geometry = state.connectors.draw(size, |frame| {
+            let transform = |point: Point| {
+                Point::new(
+                    point.x * self.scaling - origin.x,
+                    point.y * self.scaling - origin.y,
+                )
+            };
+
+            for (connector, line) in self.connectors(state, layout, appearance, region, false) {
+                draw_connector(frame, &connector, line, self.scaling, appearance, transform);
+            }
+        });
+
+        (origin, geometry)
+    }
+
+    fn minimap_geometry(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        minimap: &Minimap,
+        bounds: Rectangle,
+        appearance: &Appearance,
+    ) -> iced_graphics::widget::canvas::Geometry {
+        use iced_graphics::widget::canvas::{Frame, Path, Stroke};
+
+        let mut frame = Frame::new(bounds.size());
+
+        let offset = Vector::new(bounds.x, bounds.y);
+        let to_frame =
+            |rectangle: Rectangle| Rectangle::new(rectangle.position() - offset, rectangle.size());
+
+        let area = to_frame(minimap.bounds());
+        frame.fill_rectangle(area.position(), area.size(), appearance.minimap_background);
+
+        let node_bounds = self.all_node_bounds(state, layout);
+
+        let edges = Path::new(|p| {
+            for (from, node) in self.nodes.iter().enumerate() {
+                for edge in &node.edges {
+                    if let Some((start, end)) = self.edge_anchors(&node_bounds, from, edge) {
+                        p.move_to(minimap.project(start.position) - offset);
+                        p.line_to(minimap.project(end.position) - offset);
+                    }
+                }
+            }
+        });
+
+        frame.stroke(
+            &edges,
+            Stroke::default()
+                .with_width(1.0)
+                .with_color(appearance.minimap_edge_color),
+        );
+
+        for bounds in node_bounds {
+            let node = to_frame(minimap.project_rectangle(bounds));
+
+            frame.fill_rectangle(node.position(), node.size(), appearance.minimap_node_color);
+        }
+
+        let viewport = self.viewport(
+            layout.bounds(),
+            self.translation + state.interaction.offset(),
+        );
+
+        if let Some(viewport) = minimap
+            .project_rectangle(viewport)
+            .intersection(&minimap.bounds())
+        {
+            let viewport = to_frame(viewport);
+
+            frame.stroke(
+                &Path::rectangle(viewport.position(), viewport.size()),
+                Stroke::default()
+                    .with_width(1.0)
+                    .with_color(appearance.minimap_viewport_color),
+            );
+        }
+
+        frame.stroke(
+            &Path::rectangle(area.position(), area.size()),
+            Stroke::default()
+                .with_width(1.0)
+                .with_color(appearance.border_color),
+        );
+
+        frame.into_geometry()
+    }
+
+    fn grid(
+        &self,
+        state: &State<K>,
+        bounds: Rectangle,
+        origin: Point,
+        translation: Vector,
+        appearance: &Appearance,
+    ) -> iced_graphics::widget::canvas::Geometry {
+        use iced_graphics::widget::canvas::{Path, Stroke};
+
+        // Dense dot grids are costly to tessellate, so the grid is only
+        // rebuilt when the view or its appearance changes, not on every
+        // cursor move
+        let key = {
+            let mut hasher = DefaultHasher::new();
+
+            [
+                self.scaling,
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                origin.x,
+                origin.y,
+                translation.x,
+                translation.y,
+                appearance.grid_spacing,
+            ]
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+
+            for color in [appearance.grid_color, appearance.grid_major_color] {
+                color.into_linear().map(f32::to_bits).hash(&mut hasher);
+            }
+
+            appearance.grid.hash(&mut hasher);
+            appearance.grid_subdivisions.hash(&mut hasher);
+
+            hasher.finish()
+        };
+
+        if state.grid_key.get() != key {
+            state.grid.clear();
+            state.grid_key.set(key);
+        }
+
+        state.grid.draw(bounds.size(), |frame| {
+            if appearance.grid == Grid::None || appearance.grid_spacing <= 0.0 {
+                return;
+            }
+
+            let subdivisions = appearance.grid_subdivisions.max(1) as i64;
+            let mut spacing = appearance.grid_spacing;
+
+            // Coarsen the grid while zooming out so lines never turn into noise,
+            // keeping major lines aligned across levels
+            while spacing * self.scaling < Self::MIN_GRID_SPACING {
+                spacing *= subdivisions.max(2) as f32;
+            }
+
+            // Lines are anchored to the editor's origin, where node offsets and
+            // grid snapping are measured from
+            let range = |start: f32, length: f32, origin: f32, translation: f32| {
+                let first = ((start - origin) / self.scaling - translation) / spacing;
+                let last = ((start + length - origin) / self.scaling - translation) / spacing;
+
+                first.floor() as i64..=last.ceil() as i64
+            };
+
+            let columns = range(bounds.x, bounds.width, origin.x, translation.x);
+            let rows = range(bounds.y, bounds.height, origin.y, translation.y);
+
+            let x = |column: i64| {
+                origin.x + (column as f32 * spacing + translation.x) * self.scaling - bounds.x
+            };
+            let y = |row: i64| {
+                origin.y + (row as f32 * spacing + translation.y) * self.scaling - bounds.y
+            };
+            let is_major = |index: i64| index.rem_euclid(subdivisions) == 0;
+
+            for major in [false, true] {
+                let color = if major {
+                    appearance.grid_major_color
+                } else {
+                    appearance.grid_color
+                };
+
+                match appearance.grid {
+                    Grid::Lines => {
+                        let path = Path::new(|p| {
+                            for column in columns.clone().filter(|c| is_major(*c) == major) {
+                                p.move_to(Point::new(x(column), 0.0));
+                                p.line_to(Point::new(x(column), bounds.height));
+                            }
+
+                            for row in rows.clone().filter(|r| is_major(*r) == major) {
+                                p.move_to(Point::new(0.0, y(row)));
+                                p.line_to(Point::new(bounds.width, y(row)));
+                            }
+                        });
+
+                        frame.stroke(&path, Stroke::default().with_width(1.0).with_color(color));
+                    }
+                    Grid::Dots => {
+                        let radius = if major { 1.5 } else { 1.0 };
+
+                        let path = Path::new(|p| {
+                            for column in columns.clone() {
+                                for row in rows.clone() {
+                                    if (is_major(column) && is_major(row)) == major {
+                                        p.circle(Point::new(x(column), y(row)), radius);
+                                    }
+                                }
+                            }
+                        });
+
+                        frame.fill(&path, color);
+                    }
+                    Grid::None => {}
+                }
+            }
+        })
+    }
+
+    fn zoom(&mut self, delta: f32, bounds: Rectangle, position: Point) {
+        // With discrete levels every zoom steps to the next level in its
+        // direction, otherwise the scaling changes exponentially
+        let scaling = if self.zoom_levels.is_empty() {
+            self.scaling * delta.exp()
+        } else if delta > 0.0 {
+            self.zoom_levels
+                .iter()
+                .copied()
+                .find(|level| *level > self.scaling + f32::EPSILON)
+                .unwrap_or(self.scaling)
+        } else {
+            self.zoom_levels
+                .iter()
+                .copied()
+                .rev()
+                .find(|level| *level < self.scaling - f32::EPSILON)
+                .unwrap_or(self.scaling)
+        };
+
+        self.zoom_to(scaling, bounds, position);
+    }
+
+    fn zoom_to(&mut self, scaling: f32, bounds: Rectangle, position: Point) {
+        let scaling = scaling.max(self.min_scaling).min(self.max_scaling);
+
+        // Keep the graph point under the position in place
+        let anchor = position - bounds.position();
+
+        self.translation = self.translation + anchor * (1.0 / scaling - 1.0 / self.scaling);
+        self.scaling = scaling;
+    }
+
+    fn wheel_zoom(
+        &mut self,
+        state: &mut State<K>,
+        lines: f32,
+        bounds: Rectangle,
+        position: Point,
+        shell: &mut iced_native::Shell<'_, Message>,
+    ) -> bool {
+        let can_zoom = lines < 0.0 && self.scaling > self.min_scaling
+            || lines > 0.0 && self.scaling < self.max_scaling;
+
+        // Zooming past the limits is left to the parent, e.g. a scrollable
+        if !can_zoom {
+            return false;
+        }
+
+        let delta = if self.zoom_levels.is_empty() {
+            lines * self.wheel_sensitivity
+        } else {
+            // Discrete levels step once per wheel notch, however finely a
+            // trackpad splits up its scrolling
+            if state.wheel_lines * lines < 0.0 {
+                state.wheel_lines = 0.0;
+            }
+
+            state.wheel_lines += lines;
+
+            if state.wheel_lines.abs() < 1.0 {
+                return true;
+            }
+
+            std::mem::take(&mut state.wheel_lines)
+        };
+
+        self.zoom(delta, bounds, position);
+
+        shell.publish((self.on_event)(Event::Scaled(
+            self.scaling,
+            self.translation,
+        )));
+
+        true
+    }
+
+    fn perform(
+        &mut self,
+        action: Action,
+        state: &mut State<K>,
+        layout: Layout<'_>,
+        shell: &mut iced_native::Shell<'_, Message>,
+    ) -> bool {
+        let bounds = layout.bounds();
+
+        let event = match action {
+            Action::ZoomIn => {
+                self.zoom(self.zoom_step, bounds, bounds.position());
+                Event::Scaled(self.scaling, self.translation)
+            }
+            Action::ZoomOut => {
+                self.zoom(-self.zoom_step, bounds, bounds.position());
+                Event::Scaled(self.scaling, self.translation)
+            }
+            Action::ResetZoom => {
+                self.zoom_to(1.0, bounds, bounds.position());
+                Event::Scaled(self.scaling, self.translation)
+            }
+            Action::Fit => {
+                let origin = Vector::new(-bounds.x, -bounds.y);
+
+                // Fit the selection when there is one, otherwise the whole graph
+                let nodes = self
+                    .nodes
+                    .iter()
+                    .zip(self.all_node_bounds(state, layout))
+                    .filter(|(node, _)| {
+                        state.selection.is_empty() || state.selection.contains(node.key())
+                    })
+                    .map(|(_, bounds)| bounds + origin);
+
+                match fit(
+                    nodes,
+                    bounds.size(),
+                    self.fit_padding,
+                    self.min_scaling,
+                    self.max_scaling,
+                ) {
+                    Some((scaling, translation)) => {
+                        self.scaling = scaling;
+                        self.translation = translation;
+
+                        Event::Scaled(self.scaling, self.translation)
+                    }
+                    None => return false,
+                }
+            }
+            Action::ResetView => {
+                self.translation = Vector::default();
+
+                Event::Translated(self.translation)
+            }
+            Action::Undo => Event::Undo,
+            Action::Redo => Event::Redo,
+            Action::Delete => match state.selected_edge.take() {
+                Some((from, to)) => Event::EdgeDeleteRequested { from, to },
+                None => return false,
+            },
+            // Gestures are driven by their mouse events
+            Action::Pan | Action::Select | Action::Zoom => return false,
+        };
+
+        shell.publish((self.on_event)(event));
+
+        true
     }
 }
 
-impl<'a, Message, Backend, Theme> Widget<Message, Renderer<Backend, Theme>>
-    for Editor<'a, Message, Renderer<Backend, Theme>>
+impl<'a, K, Message, Renderer> Editor<'a, K, Message, Renderer>
 where
-    Backend: iced_graphics::Backend,
+    K: Hash + Eq + Clone,
+    Renderer: text::Renderer,
+    Renderer::Theme: StyleSheet + node::StyleSheet,
+{
+    const LABEL_PADDING: f32 = 4.0;
+
+    fn labels(
+        &self,
+        state: &State<K>,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        region: Rectangle,
+    ) -> Vec<(Rectangle, &str, (Endpoint<K>, Endpoint<K>))> {
+        if self.scaling < self.label_min_scaling {
+            return vec![];
+        }
+
+        let bounds = self.all_node_bounds(state, layout);
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(from, node)| node.edges.iter().map(move |edge| (from, edge)))
+            .filter_map(|(from, edge)| {
+                let label = edge.label.as_deref()?;
+                let (start, end) = self.edge_anchors(&bounds, from, edge)?;
+                let connector = Connector::new(start, end, edge.routing.unwrap_or(self.routing));
+
+                // Glyphs are at most about as wide as the text size, which
+                // bounds how far the label can reach past its connector
+                // without measuring it
+                let text_size = f32::from(self.label_size);
+                let reach =
+                    (label.chars().count() as f32 / 2.0 + 1.0) * text_size + Self::LABEL_PADDING;
+
+                expand(connector.bounds(), reach).intersection(&region)?;
+
+                let center = connector.midpoint();
+
+                let (width, height) =
+                    renderer.measure(label, self.label_size, Default::default(), Size::INFINITY);
+
+                // Rounded ends take up half the height on each side
+                let size = Size::new(width + height, height + Self::LABEL_PADDING);
+
+                let rectangle = Rectangle::new(
+                    center - Vector::new(size.width / 2.0, size.height / 2.0),
+                    size,
+                );
+
+                rectangle.intersection(&region)?;
+
+                Some((
+                    rectangle,
+                    label,
+                    edge.endpoints(self.nodes[from].key().clone()),
+                ))
+            })
+            .collect()
+    }
+}
+
+impl<'a, K, Message, Backend, Theme> Widget<Message, Renderer<Backend, Theme>>
+    for Editor<'a, K, Message, Renderer<Backend, Theme>>
+where
+    K: Hash + Eq + Clone + 'static,
+    Backend: iced_graphics::Backend + iced_graphics::backend::Text,
     Theme: StyleSheet + node::StyleSheet,
 {
     fn tag(&self) -> tree::Tag {
-        tree::Tag::of::<Interaction>()
+        tree::Tag::of::<State<K>>()
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(Interaction::default())
+        tree::State::new(State::new(
+            self.nodes.iter().map(|node| node.key().clone()).collect(),
+        ))
     }
 
     fn children(&self) -> Vec<Tree> {
@@ -155,15 +1176,38 @@ where
     }
 
     fn diff(&self, tree: &mut Tree) {
-        tree.diff_children_custom(
-            &self.nodes,
-            |state, node| node.diff(state),
-            |node| Tree {
-                tag: node.tag(),
-                state: node.state(),
-                children: node.children(),
-            },
-        )
+        let state = tree.state.downcast_mut::<State<K>>();
+
+        // Match children by key so node state follows its node when others
+        // are inserted or removed
+        let mut previous: HashMap<K, Tree> = std::mem::take(&mut state.keys)
+            .into_iter()
+            .zip(std::mem::take(&mut tree.children))
+            .collect();
+
+        tree.children = self
+            .nodes
+            .iter()
+            .map(|node| match previous.remove(node.key()) {
+                Some(mut child) => {
+                    node.diff(&mut child);
+                    child
+                }
+                None => Tree {
+                    tag: node.tag(),
+                    state: node.state(),
+                    children: node.children(),
+                },
+            })
+            .collect();
+
+        state.keys = self.nodes.iter().map(|node| node.key().clone()).collect();
+        let selected = state.selection.len();
+        state.selection.retain(|key| self.indices.contains_key(key));
+
+        // Diffing can't publish, so removed nodes leave the selection on the
+        // next event instead
+        state.is_selection_pruned |= state.selection.len() != selected;
     }
 
     fn width(&self) -> Length {
@@ -189,6 +1233,18 @@ where
         )
     }
 
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.container(None, &mut |operation| {
+            self.nodes
+                .iter()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+                .for_each(|((node, node_state), layout)| {
+                    node.operate(node_state, layout, operation);
+                });
+        });
+    }
+
     fn on_event(
         &mut self,
         tree: &mut Tree,
@@ -199,130 +1255,420 @@ where
         clipboard: &mut dyn iced_native::Clipboard,
         shell: &mut iced_native::Shell<'_, Message>,
     ) -> event::Status {
-        let interaction = tree.state.downcast_mut::<Interaction>();
+        let state = tree.state.downcast_mut::<State<K>>();
 
         let bounds = layout.bounds();
         let contains_cursor = bounds.contains(cursor_position);
 
-        let transformed_cursor = self.transform_cursor(cursor_position);
+        let transformed_cursor = self.transform_cursor(bounds, cursor_position);
+
+        if state.is_selection_pruned {
+            state.is_selection_pruned = false;
+
+            shell.publish((self.on_event)(Event::SelectionChanged(
+                state.selection.clone(),
+            )));
+        }
+
+        if let event::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            state.modifiers = modifiers;
+        }
+
+        // Clicking anywhere else hands the keyboard back to the rest of the
+        // application
+        if let event::Event::Mouse(mouse::Event::ButtonPressed(_)) = event {
+            state.is_focused = contains_cursor;
+        }
+
+        if let event::Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code: keyboard::KeyCode::Escape,
+            ..
+        }) = event
+        {
+            if matches!(
+                state.interaction,
+                Interaction::Connecting { .. }
+                    | Interaction::Selecting { .. }
+                    | Interaction::Moving { .. }
+            ) {
+                state.interaction = Interaction::Idle;
+                return event::Status::Captured;
+            } else if state.is_focused {
+                state.is_focused = false;
+                return event::Status::Captured;
+            }
+        }
+
+        match &mut state.interaction {
+            Interaction::Navigating { .. } => match event {
+                event::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    if let Some(minimap) = self.minimap_at(state, layout) {
+                        state.interaction = Interaction::Navigating {
+                            offset: self.navigate(bounds, minimap.unproject(cursor_position)),
+                        };
+                    }
+
+                    return event::Status::Captured;
+                }
+                event::Event::Mouse(mouse::Event::ButtonReleased(button))
+                    if self.bindings.is_button(button) =>
+                {
+                    let offset = state.interaction.offset();
+                    state.interaction = Interaction::Idle;
+
+                    shell.publish((self.on_event)(Event::Translated(
+                        self.translation + offset,
+                    )));
+
+                    return event::Status::Captured;
+                }
+                _ => {}
+            },
+            Interaction::Connecting { from, cursor } => match event {
+                event::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    *cursor = transformed_cursor;
+                    return event::Status::Captured;
+                }
+                event::Event::Mouse(mouse::Event::ButtonReleased(button))
+                    if self.bindings.is_button(button) =>
+                {
+                    let from = from.clone();
+                    state.interaction = Interaction::Idle;
+
+                    if let Some(to) = self
+                        .connection_target(state, layout, transformed_cursor)
+                        .filter(|to| to.node != from.node)
+                    {
+                        shell.publish((self.on_event)(Event::EdgeCreated { from, to }));
+                    }
+
+                    return event::Status::Captured;
+                }
+                _ => {}
+            },
+            Interaction::Selecting {
+                button,
+                started_at,
+                cursor,
+            } => match event {
+                event::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    *cursor = transformed_cursor;
+                    return event::Status::Captured;
+                }
+                event::Event::Mouse(mouse::Event::ButtonReleased(released))
+                    if released == *button =>
+                {
+                    let area = area(*started_at, *cursor);
+                    state.interaction = Interaction::Idle;
+
+                    let selection = self
+                        .nodes
+                        .iter()
+                        .zip(self.all_node_bounds(state, layout))
+                        .filter(|(_, bounds)| bounds.intersection(&area).is_some())
+                        .map(|(node, _)| node.key().clone())
+                        .collect();
+
+                    self.select(&mut state.selection, selection, shell);
+
+                    return event::Status::Captured;
+                }
+                _ => {}
+            },
+            Interaction::Moving {
+                grabbed,
+                started_at,
+                offset,
+            } => match event {
+                // Pressing or releasing Alt mid-drag snaps or frees the
+                // selection right away
+                event::Event::Mouse(mouse::Event::CursorMoved { .. })
+                | event::Event::Keyboard(keyboard::Event::ModifiersChanged(_)) => {
+                    *offset = transformed_cursor - *started_at;
+
+                    // The grabbed node lands on the grid, the rest of the
+                    // selection keeps its position relative to it
+                    if !state.modifiers.alt() {
+                        if let Some(index) = self.index_of(grabbed) {
+                            *offset = self.snap(self.nodes[index].offset(), *offset);
+                        }
+                    }
+
+                    return event::Status::Captured;
+                }
+                event::Event::Mouse(mouse::Event::ButtonReleased(button))
+                    if self.bindings.is_button(button) =>
+                {
+                    let offset = *offset;
+                    state.interaction = Interaction::Idle;
+
+                    if offset != Vector::default() {
+                        let moved = state
+                            .selection
+                            .iter()
+                            .filter_map(|key| {
+                                let node = &self.nodes[self.index_of(key)?];
+
+                                Some((key.clone(), node.offset() + offset))
+                            })
+                            .collect();
+
+                        shell.publish((self.on_event)(Event::NodesMoved(moved)));
+                    }
+
+                    return event::Status::Captured;
+                }
+                _ => {}
+            },
+            _ if contains_cursor => match event {
+                event::Event::Mouse(mouse::Event::ButtonPressed(button))
+                    if self.bindings.is_button(button) =>
+                {
+                    if let Some(minimap) = self
+                        .minimap_at(state, layout)
+                        .filter(|minimap| minimap.bounds().contains(cursor_position))
+                    {
+                        state.interaction = Interaction::Navigating {
+                            offset: self.navigate(bounds, minimap.unproject(cursor_position)),
+                        };
+                        return event::Status::Captured;
+                    }
+
+                    // Labels are drawn above everything else in the graph
+                    let visible = self.viewport(bounds, self.translation);
+
+                    if let Some((_, _, (from, to))) = self
+                        .labels(state, layout, renderer, visible)
+                        .into_iter()
+                        .rev()
+                        .find(|(bounds, ..)| bounds.contains(transformed_cursor))
+                    {
+                        shell.publish((self.on_event)(Event::EdgeLabelClicked { from, to }));
+                        return event::Status::Captured;
+                    }
+
+                    if let Some(from) =
+                        self.port_at(state, layout, transformed_cursor, PortKind::Output)
+                    {
+                        state.interaction = Interaction::Connecting {
+                            from,
+                            cursor: transformed_cursor,
+                        };
+                        return event::Status::Captured;
+                    }
+
+                    state.selected_edge = self.edge_at(state, layout, transformed_cursor);
+
+                    if let Some((from, to)) = state.selected_edge.clone() {
+                        shell.publish((self.on_event)(Event::EdgeSelected { from, to }));
+                        return event::Status::Captured;
+                    }
+
+                    if let Some(index) = self.hovered_node(&tree.children) {
+                        let key = self.nodes[index].key().clone();
+                        let mut selection = state.selection.clone();
+
+                        if self.bindings.is_toggle(state.modifiers) {
+                            if let Some(position) = selection.iter().position(|k| *k == key) {
+                                selection.remove(position);
+                            } else {
+                                selection.push(key.clone());
+                            }
+                        } else if !selection.contains(&key) {
+                            selection = vec![key.clone()];
+                        }
+
+                        self.select(&mut state.selection, selection, shell);
+
+                        // Dragging any selected node moves the whole selection
+                        if state.selection.contains(&key) {
+                            state.interaction = Interaction::Moving {
+                                grabbed: key,
+                                started_at: transformed_cursor,
+                                offset: Vector::default(),
+                            };
+                        }
+
+                        return event::Status::Captured;
+                    }
+                }
+                event::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    state.hovered_edge = self.edge_at(state, layout, transformed_cursor);
+                }
+                _ => {}
+            },
+            _ => {
+                if let event::Event::Mouse(mouse::Event::CursorMoved { .. }) = event {
+                    state.hovered_edge = None;
+                }
+            }
+        }
+
+        // Off-screen nodes can't be under the cursor, so they only receive mouse
+        // events while still hovered, to notice the cursor has left
+        let visible = self.viewport(bounds, self.translation + state.interaction.offset());
+        let is_mouse_event = matches!(event, event::Event::Mouse(_));
+
+        let dispatch: Vec<bool> = tree
+            .children
+            .iter()
+            .zip(layout.children())
+            .enumerate()
+            .map(|(index, (child, layout))| {
+                !is_mouse_event
+                    || matches!(
+                        child.state.downcast_ref::<node::State>(),
+                        node::State::Hovered
+                    )
+                    || self.is_visible(state, index, layout.bounds(), &visible)
+            })
+            .collect();
 
         let status = self
             .nodes
             .iter_mut()
             .zip(&mut tree.children)
             .zip(layout.children())
-            .enumerate()
-            .map(|(index, ((node, state), layout))| {
+            .zip(dispatch)
+            .filter(|(_, dispatch)| *dispatch)
+            .map(|(((node, child), layout), _)| {
                 node.on_event(
-                    state,
+                    child,
                     event.clone(),
                     layout,
                     transformed_cursor,
                     renderer,
                     clipboard,
                     shell,
-                    index,
-                    &self.on_event,
                 )
             })
             .fold(event::Status::Ignored, event::Status::merge);
 
-        if matches!(status, event::Status::Ignored) && contains_cursor {
-            match event {
-                event::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
-                    *interaction = Interaction::Translating {
+        // A press taken by node content, like a text input, focuses that
+        // content instead of the editor
+        if let (event::Event::Mouse(mouse::Event::ButtonPressed(_)), event::Status::Captured) =
+            (&event, status)
+        {
+            state.is_focused = false;
+        }
+
+        // Shortcuts follow focus rather than the cursor, so they don't steal
+        // keystrokes meant for widgets elsewhere
+        let is_target = match event {
+            event::Event::Keyboard(_) => state.is_focused,
+            _ => contains_cursor,
+        };
+
+        if matches!(status, event::Status::Ignored) && is_target {
+            let chord = match event {
+                event::Event::Mouse(mouse::Event::ButtonPressed(button)) => {
+                    Some(Chord::Mouse(button, state.modifiers))
+                }
+                event::Event::Mouse(mouse::Event::WheelScrolled { .. }) => {
+                    Some(Chord::Wheel(state.modifiers))
+                }
+                event::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code,
+                    modifiers,
+                }) => Some(Chord::Key(key_code, modifiers)),
+                _ => None,
+            };
+
+            let action = chord.and_then(|chord| self.bindings.action(chord));
+
+            match (event, action) {
+                (
+                    event::Event::Mouse(mouse::Event::ButtonPressed(button)),
+                    Some(Action::Select),
+                ) => {
+                    state.interaction = Interaction::Selecting {
+                        button,
+                        started_at: transformed_cursor,
+                        cursor: transformed_cursor,
+                    };
+
+                    return event::Status::Captured;
+                }
+                (event::Event::Mouse(mouse::Event::ButtonPressed(button)), Some(Action::Pan)) => {
+                    if self.bindings.is_button(button) {
+                        self.select(&mut state.selection, vec![], shell);
+                    }
+
+                    state.interaction = Interaction::Translating {
+                        button,
                         started_at: cursor_position,
                         offset: Vector::default(),
                     };
+
                     return event::Status::Captured;
                 }
-                event::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
-                    if let Interaction::Translating { offset, .. } = interaction {
-                        shell.publish((self.on_event)(Event::Translated(
-                            self.translation + *offset,
-                        )));
+                // Clicking empty canvas still clears the selection when
+                // dragging with the button is unbound
+                (event::Event::Mouse(mouse::Event::ButtonPressed(button)), None)
+                    if self.bindings.is_button(button) =>
+                {
+                    self.select(&mut state.selection, vec![], shell);
 
-                        *interaction = Interaction::Idle;
-                        return event::Status::Captured;
-                    }
-                }
-                event::Event::Mouse(mouse::Event::CursorMoved { position }) => {
-                    if let Interaction::Translating { started_at, offset } = interaction {
-                        *offset = (position - *started_at) * (1.0 / self.scaling);
-                        return event::Status::Captured;
-                    }
+                    return event::Status::Captured;
                 }
-                event::Event::Mouse(mouse::Event::WheelScrolled { delta }) => match delta {
-                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
-                        if y < 0.0 && self.scaling > Self::MIN_SCALING
-                            || y > 0.0 && self.scaling < Self::MAX_SCALING
-                        {
-                            self.zoom(y, cursor_position);
-
-                            shell.publish((self.on_event)(Event::Scaled(
-                                self.scaling,
-                                self.translation,
+                (event::Event::Mouse(mouse::Event::ButtonReleased(released)), _) => {
+                    if let Interaction::Translating { button, offset, .. } = &mut state.interaction
+                    {
+                        if *button == released {
+                            shell.publish((self.on_event)(Event::Translated(
+                                self.translation + *offset,
                             )));
 
+                            state.interaction = Interaction::Idle;
                             return event::Status::Captured;
                         }
                     }
-                },
-                event::Event::Keyboard(keyboard::Event::KeyPressed {
-                    key_code: keyboard::KeyCode::Minus,
-                    ..
-                }) => {
-                    self.zoom(-1.0, bounds.position());
-
-                    shell.publish((self.on_event)(Event::Scaled(
-                        self.scaling,
-                        self.translation,
-                    )));
-
-                    return event::Status::Captured;
                 }
-                event::Event::Keyboard(keyboard::Event::KeyPressed {
-                    key_code: keyboard::KeyCode::Equals,
-                    ..
-                }) => {
-                    self.zoom(1.0, bounds.position());
-
-                    shell.publish((self.on_event)(Event::Scaled(
-                        self.scaling,
-                        self.translation,
-                    )));
-
-                    return event::Status::Captured;
+                (event::Event::Mouse(mouse::Event::CursorMoved { position }), _) => {
+                    if let Interaction::Translating {
+                        started_at, offset, ..
+                    } = &mut state.interaction
+                    {
+                        *offset = (position - *started_at) * (1.0 / self.scaling);
+                        return event::Status::Captured;
+                    }
                 }
-                event::Event::Keyboard(keyboard::Event::KeyPressed {
-                    key_code: keyboard::KeyCode::Key0,
-                    ..
-                }) => {
-                    self.reset_zoom(bounds.position());
-
-                    shell.publish((self.on_event)(Event::Scaled(
-                        self.scaling,
-                        self.translation,
-                    )));
+                (
+                    event::Event::Mouse(mouse::Event::WheelScrolled { delta }),
+                    Some(Action::Zoom),
+                ) => {
+                    let lines = match delta {
+                        mouse::ScrollDelta::Lines { y, .. } => y,
+                        mouse::ScrollDelta::Pixels { y, .. } => y / Self::PIXELS_PER_LINE,
+                    };
 
-                    return event::Status::Captured;
+                    if self.wheel_zoom(state, lines, bounds, cursor_position, shell) {
+                        return event::Status::Captured;
+                    }
                 }
-                event::Event::Keyboard(keyboard::Event::KeyPressed {
-                    key_code: keyboard::KeyCode::Space,
-                    ..
-                }) => {
-                    let factor = self.scaling - 1.0;
-
-                    self.translation = Vector::default()
-                        - Vector::new(
-                            bounds.x * factor / self.scaling,
-                            bounds.y * factor / self.scaling,
-                        );
+                // Trackpads swipe to pan in both directions, whatever the
+                // unit, and only zoom through the chord bound to it
+                (event::Event::Mouse(mouse::Event::WheelScrolled { delta }), _)
+                    if self.bindings.is_trackpad() =>
+                {
+                    let delta = match delta {
+                        mouse::ScrollDelta::Lines { x, y } => {
+                            Vector::new(x, y) * Self::PIXELS_PER_LINE
+                        }
+                        mouse::ScrollDelta::Pixels { x, y } => Vector::new(x, y),
+                    };
+
+                    self.translation = self.translation + delta * (1.0 / self.scaling);
 
                     shell.publish((self.on_event)(Event::Translated(self.translation)));
 
                     return event::Status::Captured;
                 }
+                (_, Some(action)) => {
+                    if self.perform(action, state, layout, shell) {
+                        return event::Status::Captured;
+                    }
+                }
                 _ => {}
             }
 
@@ -342,9 +1688,10 @@ where
         cursor_position: Point,
         viewport: &Rectangle,
     ) {
-        let interaction = tree.state.downcast_ref::<Interaction>();
+        let state = tree.state.downcast_ref::<State<K>>();
+        let interaction = &state.interaction;
 
-        let transformed_cursor = self.transform_cursor(cursor_position);
+        let transformed_cursor = self.transform_cursor(layout.bounds(), cursor_position);
 
         let appearance = <Theme as StyleSheet>::appearance(theme, self.style);
 
@@ -369,111 +1716,193 @@ where
 
         let padded_bounds = pad(layout.bounds(), 1.0);
 
+        let frame_offset = Vector::new(padded_bounds.x, padded_bounds.y);
+
+        let visible = self.viewport(layout.bounds(), self.translation + interaction.offset());
+        let translation = self.absolute(layout.bounds(), self.translation + interaction.offset());
+
+        // Drawn in the base layer, so it stays beneath the node layer
+        let grid = self.grid(
+            state,
+            padded_bounds,
+            layout.bounds().position(),
+            self.translation + interaction.offset(),
+            &appearance,
+        );
+
+        renderer.with_translation(frame_offset, |renderer| {
+            renderer.draw_primitive(grid.into_primitive());
+        });
+
         renderer.with_layer(padded_bounds, |renderer| {
-            renderer.with_translation(self.translation + interaction.offset(), |renderer| {
+            renderer.with_translation(translation, |renderer| {
                 renderer.with_scale(self.scaling, |renderer| {
                     self.nodes
                         .iter()
                         .zip(&tree.children)
                         .zip(layout.children())
-                        .for_each(|((node, state), layout)| {
-                            node.draw(
-                                state,
-                                renderer,
-                                theme,
-                                style,
-                                layout,
-                                transformed_cursor,
-                                viewport,
-                            )
+                        .enumerate()
+                        .filter(|(index, (_, layout))| {
+                            self.is_visible(state, *index, layout.bounds(), &visible)
+                        })
+                        .for_each(|(_, ((node, node_state), layout))| {
+                            let draw = |renderer: &mut Renderer<Backend, Theme>| {
+                                node.draw(
+                                    node_state,
+                                    renderer,
+                                    theme,
+                                    style,
+                                    layout,
+                                    transformed_cursor,
+                                    viewport,
+                                    state.selection.contains(node.key()),
+                                )
+                            };
+
+                            let offset = state.node_offset(node.key());
+
+                            if offset == Vector::default() {
+                                draw(renderer);
+                            } else {
+                                renderer.with_translation(offset, draw);
+                            }
                         });
                 });
             });
 
-            let frame_offset = Vector::new(padded_bounds.x, padded_bounds.y);
+            let (origin, geometry) = self.cached_connectors(state, layout, visible, &appearance);
+
+            renderer.with_translation(origin + translation * self.scaling, |renderer| {
+                renderer.draw_primitive(geometry.into_primitive());
+            });
+
             renderer.with_translation(frame_offset, |renderer| {
-                use iced_graphics::widget::canvas::{Frame, Path, Stroke};
+                use iced_graphics::widget::canvas::Frame;
 
-                self.nodes
-                    .iter()
-                    .enumerate()
-                    .for_each(|(from_index, from)| {
-                        for to_index in from.edges.iter().copied() {
-                            if self.nodes.get(to_index).is_some() {
-                                let from_state = tree
-                                    .children
-                                    .get(from_index)
-                                    .unwrap()
-                                    .state
-                                    .downcast_ref::<node::State>();
-                                let to_state = tree
-                                    .children
-                                    .get(to_index)
-                                    .unwrap()
-                                    .state
-                                    .downcast_ref::<node::State>();
-
-                                let from_bounds = from_state.adjusted_bounds(
-                                    layout.children().nth(from_index).unwrap().bounds(),
-                                );
-                                let to_bounds = to_state.adjusted_bounds(
-                                    layout.children().nth(to_index).unwrap().bounds(),
-                                );
+                let transform_point = |point: Point| {
+                    let translated = point + translation;
 
-                                let mut frame = Frame::new(padded_bounds.size());
-
-                                let transform_point = |point: Point| {
-                                    let translated =
-                                        point + self.translation + interaction.offset();
-
-                                    Point {
-                                        x: translated.x * self.scaling,
-                                        y: translated.y * self.scaling,
-                                    } - frame_offset
-                                };
-
-                                let start_untransformed = Point {
-                                    x: (from_bounds.x + from_bounds.width),
-                                    y: from_bounds.center_y(),
-                                };
-                                let start = transform_point(start_untransformed);
-                                let end_untransformed = Point {
-                                    x: to_bounds.x,
-                                    y: to_bounds.center_y(),
-                                };
-                                let end = transform_point(end_untransformed);
-
-                                let path = Path::new(|p| {
-                                    let control_scale =
-                                        ((end_untransformed.x - start_untransformed.x) / 2.0)
-                                            .max(30.0)
-                                            * self.scaling;
-                                    let control_a = Point {
-                                        x: start.x + control_scale,
-                                        y: start.y,
-                                    };
-                                    let control_b = Point {
-                                        x: end.x - control_scale,
-                                        y: end.y,
-                                    };
-
-                                    p.move_to(start);
-                                    p.bezier_curve_to(control_a, control_b, end);
-                                });
+                    Point {
+                        x: translated.x * self.scaling,
+                        y: translated.y * self.scaling,
+                    } - frame_offset
+                };
+
+                let mut frame = Frame::new(padded_bounds.size());
+
+                let mut draw_live = |connector: Connector, line: Line| {
+                    draw_connector(
+                        &mut frame,
+                        &connector,
+                        line,
+                        self.scaling,
+                        &appearance,
+                        transform_point,
+                    );
+                };
+
+                for (connector, line) in self.connectors(state, layout, &appearance, visible, true)
+                {
+                    draw_live(connector, line);
+                }
+
+                if let Interaction::Connecting { from, cursor } = interaction {
+                    let start = self.index_of(&from.node).and_then(|index| {
+                        self.nodes[index].anchor(
+                            self.node_bounds(state, layout, index),
+                            PortKind::Output,
+                            from.port,
+                        )
+                    });
+
+                    if let Some(start) = start {
+                        draw_live(
+                            Connector::new(
+                                start,
+                                Anchor {
+                                    position: *cursor,
+                                    side: start.side.opposite(),
+                                },
+                                self.routing,
+                            ),
+                            Line {
+                                color: appearance.connector_color,
+                                width: appearance.connector_width,
+                                dash: &[],
+                                offset: 0.0,
+                            },
+                        );
+                    }
+                }
+
+                renderer.draw_primitive(frame.into_geometry().into_primitive());
+            });
 
-                                frame.stroke(
-                                    &path,
-                                    Stroke::default()
-                                        .with_width(appearance.connector_width * self.scaling)
-                                        .with_color(appearance.connector_color),
+            let labels = self.labels(state, layout, renderer, visible);
+
+            if !labels.is_empty() {
+                renderer.with_layer(padded_bounds, |renderer| {
+                    renderer.with_translation(translation, |renderer| {
+                        renderer.with_scale(self.scaling, |renderer| {
+                            for (bounds, label, _) in labels {
+                                renderer.fill_quad(
+                                    renderer::Quad {
+                                        bounds,
+                                        border_radius: bounds.height / 2.0,
+                                        border_width: 1.0,
+                                        border_color: appearance.label_border_color,
+                                    },
+                                    appearance.label_background,
                                 );
 
-                                let primitive = frame.into_geometry().into_primitive();
-                                renderer.draw_primitive(primitive);
+                                renderer.fill_text(text::Text {
+                                    content: label,
+                                    bounds: Rectangle {
+                                        x: bounds.center_x(),
+                                        y: bounds.center_y(),
+                                        ..bounds
+                                    },
+                                    size: f32::from(self.label_size),
+                                    color: appearance.label_text_color,
+                                    font: Default::default(),
+                                    horizontal_alignment: alignment::Horizontal::Center,
+                                    vertical_alignment: alignment::Vertical::Center,
+                                });
                             }
-                        }
+                        });
                     });
-            });
+                });
+            }
+
+            if let Interaction::Selecting {
+                started_at, cursor, ..
+            } = interaction
+            {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: area(
+                            self.project(layout.bounds(), *started_at),
+                            self.project(layout.bounds(), *cursor),
+                        ),
+                        border_radius: 0.0,
+                        border_width: 1.0,
+                        border_color: appearance.selection_border_color,
+                    },
+                    appearance.selection_background,
+                );
+            }
+
+            // A layer of its own keeps the minimap above node contents
+            if let Some(minimap) = self.minimap_at(state, layout) {
+                let geometry =
+                    self.minimap_geometry(state, layout, &minimap, padded_bounds, &appearance);
+
+                renderer.with_layer(minimap.bounds(), |renderer| {
+                    renderer.with_translation(frame_offset, |renderer| {
+                        renderer.draw_primitive(geometry.into_primitive());
+                    });
+                });
+            }
         });
     }
 
@@ -485,32 +1914,247 @@ where
         viewport: &Rectangle,
         renderer: &Renderer<Backend, Theme>,
     ) -> iced_native::mouse::Interaction {
-        let transformed_cursor = self.transform_cursor(cursor_position);
+        let state = tree.state.downcast_ref::<State<K>>();
+
+        let transformed_cursor = self.transform_cursor(layout.bounds(), cursor_position);
+
+        let visible = self.viewport(
+            layout.bounds(),
+            self.translation + state.interaction.offset(),
+        );
+
+        if matches!(
+            state.interaction,
+            Interaction::Moving { .. } | Interaction::Navigating { .. }
+        ) {
+            return mouse::Interaction::Grabbing;
+        }
+
+        if self
+            .minimap_at(state, layout)
+            .map_or(false, |minimap| minimap.bounds().contains(cursor_position))
+        {
+            return mouse::Interaction::Pointer;
+        }
+
+        if matches!(state.interaction, Interaction::Connecting { .. })
+            || layout.bounds().contains(cursor_position)
+                && self
+                    .port_at(state, layout, transformed_cursor, PortKind::Output)
+                    .is_some()
+        {
+            return mouse::Interaction::Crosshair;
+        }
 
         self.nodes
             .iter()
             .zip(&tree.children)
             .zip(layout.children())
-            .map(|((node, state), layout)| {
-                node.mouse_interaction(state, layout, transformed_cursor, viewport, renderer)
+            .enumerate()
+            .filter(|(index, (_, layout))| {
+                self.is_visible(state, *index, layout.bounds(), &visible)
+            })
+            .map(|(_, ((node, node_state), layout))| {
+                node.mouse_interaction(node_state, layout, transformed_cursor, viewport, renderer)
             })
             .max()
             .unwrap_or_default()
     }
+
+    fn overlay<'b>(
+        &'b self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer<Backend, Theme>,
+    ) -> Option<overlay::Element<'b, Message, Renderer<Backend, Theme>>> {
+        let state = tree.state.downcast_ref::<State<K>>();
+        let translation = self.absolute(
+            layout.bounds(),
+            self.translation + state.interaction.offset(),
+        );
+
+        // More than one node can have an overlay open, so none are dropped
+        let children: Vec<_> = self
+            .nodes
+            .iter()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .filter_map(|((node, node_state), layout)| {
+                // Overlays are drawn untransformed, so node content lays
+                // them out against where it appears on screen
+                let offset = translation + state.node_offset(node.key());
+                let projected = project_layout(layout, Point::ORIGIN, self.scaling)
+                    .translate(offset * self.scaling);
+
+                node.overlay(node_state, Layout::new(&projected), renderer)
+            })
+            .collect();
+
+        (!children.is_empty()).then(|| overlay::Group::with_children(children).overlay())
+    }
 }
 
-impl<'a, Message, Backend, Theme> From<Editor<'a, Message, Renderer<Backend, Theme>>>
+impl<'a, K, Message, Backend, Theme> From<Editor<'a, K, Message, Renderer<Backend, Theme>>>
     for Element<'a, Message, Renderer<Backend, Theme>>
 where
-    Backend: iced_graphics::Backend + 'a,
+    K: Hash + Eq + Clone + 'static,
+    Backend: iced_graphics::Backend + iced_graphics::backend::Text + 'a,
     Theme: StyleSheet + node::StyleSheet + 'a,
     Message: 'a,
 {
-    fn from(editor: Editor<'a, Message, Renderer<Backend, Theme>>) -> Self {
+    fn from(editor: Editor<'a, K, Message, Renderer<Backend, Theme>>) -> Self {
         Element::new(editor)
     }
 }
 
+pub fn fit(
+    nodes: impl IntoIterator<Item = Rectangle>,
+    viewport: Size,
+    padding: f32,
+    min_scaling: f32,
+    max_scaling: f32,
+) -> Option<(f32, Vector)> {
+    let content = nodes.into_iter().reduce(union)?;
+
+    let available = Size::new(
+        (viewport.width - padding * 2.0).max(1.0),
+        (viewport.height - padding * 2.0).max(1.0),
+    );
+
+    let scaling = (available.width / content.width)
+        .min(available.height / content.height)
+        .max(min_scaling)
+        .min(max_scaling);
+
+    let translation = Vector::new(
+        viewport.width / 2.0 / scaling - content.center_x(),
+        viewport.height / 2.0 / scaling - content.center_y(),
+    );
+
+    Some((scaling, translation))
+}
+
+fn area(a: Point, b: Point) -> Rectangle {
+    Rectangle::new(
+        Point::new(a.x.min(b.x), a.y.min(b.y)),
+        Size::new((a.x - b.x).abs(), (a.y - b.y).abs()),
+    )
+}
+
+fn project_layout(layout: Layout<'_>, parent: Point, scaling: f32) -> layout::Node {
+    let bounds = layout.bounds();
+
+    let children = layout
+        .children()
+        .map(|child| project_layout(child, bounds.position(), scaling))
+        .collect();
+
+    layout::Node::with_children(
+        Size::new(bounds.width * scaling, bounds.height * scaling),
+        children,
+    )
+    .translate((bounds.position() - parent) * scaling)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line<'a> {
+    color: Color,
+    width: f32,
+    dash: &'a [f32],
+    offset: f32,
+}
+
+impl Line<'_> {
+    fn reach(&self, appearance: &Appearance) -> f32 {
+        self.width.max(appearance.marker_size)
+    }
+}
+
+impl Hash for Line<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Color { r, g, b, a } = self.color;
+
+        [r, g, b, a, self.width, self.offset]
+            .map(f32::to_bits)
+            .hash(state);
+
+        for segment in self.dash {
+            segment.to_bits().hash(state);
+        }
+    }
+}
+
+fn draw_connector(
+    frame: &mut iced_graphics::widget::canvas::Frame,
+    connector: &Connector,
+    line: Line<'_>,
+    scaling: f32,
+    appearance: &Appearance,
+    transform: impl Fn(Point) -> Point,
+) {
+    use iced_graphics::widget::canvas::Stroke;
+
+    let stroke = || {
+        Stroke::default()
+            .with_width(line.width * scaling)
+            .with_color(line.color)
+    };
+
+    // Dashes are laid out in graph units, so they scale with the connector
+    let path = if line.dash.is_empty() {
+        connector.path(&transform)
+    } else {
+        connector.dashed(line.dash, line.offset, &transform)
+    };
+
+    frame.stroke(&path, stroke());
+
+    let (filled, outlined) = connector.markers(
+        appearance.source_marker,
+        appearance.target_marker,
+        appearance.marker_size,
+        &transform,
+    );
+
+    frame.fill(&filled, line.color);
+    frame.stroke(&outlined, stroke());
+}
+
+fn contains(outer: Rectangle, inner: Rectangle) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+fn expand(rectangle: Rectangle, amount: f32) -> Rectangle {
+    Rectangle {
+        x: rectangle.x - amount,
+        y: rectangle.y - amount,
+        width: rectangle.width + amount * 2.0,
+        height: rectangle.height + amount * 2.0,
+    }
+}
+
+fn union(a: Rectangle, b: Rectangle) -> Rectangle {
+    let x = a.x.min(b.x);
+    let y = a.y.min(b.y);
+
+    Rectangle {
+        x,
+        y,
+        width: (a.x + a.width).max(b.x + b.width) - x,
+        height: (a.y + a.height).max(b.y + b.height) - y,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Grid {
+    None,
+    Dots,
+    Lines,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Appearance {
     pub background: Option<Background>,
@@ -519,6 +2163,25 @@ pub struct Appearance {
     pub border_color: Color,
     pub connector_width: f32,
     pub connector_color: Color,
+    pub hovered_connector_color: Color,
+    pub selected_connector_color: Color,
+    pub selection_background: Background,
+    pub selection_border_color: Color,
+    pub grid: Grid,
+    pub grid_spacing: f32,
+    pub grid_subdivisions: u16,
+    pub grid_color: Color,
+    pub grid_major_color: Color,
+    pub minimap_background: Color,
+    pub minimap_node_color: Color,
+    pub minimap_edge_color: Color,
+    pub minimap_viewport_color: Color,
+    pub source_marker: Marker,
+    pub target_marker: Marker,
+    pub marker_size: f32,
+    pub label_text_color: Color,
+    pub label_background: Background,
+    pub label_border_color: Color,
 }
 
 impl Default for Appearance {
@@ -530,6 +2193,25 @@ impl Default for Appearance {
             border_color: Color::TRANSPARENT,
             connector_width: 1.0,
             connector_color: Color::BLACK,
+            hovered_connector_color: Color::from_rgb(0.4, 0.4, 0.4),
+            selected_connector_color: Color::from_rgb(0.2, 0.4, 1.0),
+            selection_background: Color::from_rgba(0.2, 0.4, 1.0, 0.2).into(),
+            selection_border_color: Color::from_rgb(0.2, 0.4, 1.0),
+            grid: Grid::None,
+            grid_spacing: 20.0,
+            grid_subdivisions: 5,
+            grid_color: Color::from_rgba(0.0, 0.0, 0.0, 0.05),
+            grid_major_color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+            minimap_background: Color::WHITE,
+            minimap_node_color: Color::from_rgb(0.6, 0.6, 0.6),
+            minimap_edge_color: Color::from_rgb(0.8, 0.8, 0.8),
+            minimap_viewport_color: Color::from_rgb(0.2, 0.4, 1.0),
+            source_marker: Marker::None,
+            target_marker: Marker::None,
+            marker_size: 8.0,
+            label_text_color: Color::BLACK,
+            label_background: Color::WHITE.into(),
+            label_border_color: Color::from_rgb(0.6, 0.6, 0.6),
         }
     }
 }
@@ -539,3 +2221,57 @@ pub trait StyleSheet {
 
     fn appearance(&self, style: Self::Style) -> Appearance;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_centres_the_nodes() {
+        let nodes = [
+            Rectangle::new(Point::new(100.0, 100.0), Size::new(50.0, 50.0)),
+            Rectangle::new(Point::new(150.0, 125.0), Size::new(50.0, 25.0)),
+        ];
+
+        let (scaling, translation) = fit(nodes, Size::new(400.0, 200.0), 0.0, 0.1, 10.0).unwrap();
+
+        assert_eq!(scaling, 4.0);
+        assert_eq!(translation, Vector::new(-100.0, -100.0));
+
+        // The centre of the nodes lands on the centre of the viewport
+        assert_eq!((150.0 + translation.x) * scaling, 200.0);
+        assert_eq!((125.0 + translation.y) * scaling, 100.0);
+    }
+
+    #[test]
+    fn fit_leaves_padding_around_the_nodes() {
+        let nodes = [Rectangle::new(Point::ORIGIN, Size::new(100.0, 100.0))];
+
+        let (scaling, _) = fit(nodes, Size::new(300.0, 300.0), 50.0, 0.1, 10.0).unwrap();
+
+        assert_eq!(scaling, 2.0);
+    }
+
+    #[test]
+    fn fit_clamps_the_scaling() {
+        let small = [Rectangle::new(Point::ORIGIN, Size::new(10.0, 10.0))];
+        let (scaling, translation) = fit(small, Size::new(400.0, 400.0), 0.0, 0.5, 2.0).unwrap();
+
+        assert_eq!(scaling, 2.0);
+        assert_eq!(translation, Vector::new(95.0, 95.0));
+
+        let large = [Rectangle::new(Point::ORIGIN, Size::new(10_000.0, 10_000.0))];
+        let (scaling, translation) = fit(large, Size::new(100.0, 100.0), 0.0, 0.5, 2.0).unwrap();
+
+        assert_eq!(scaling, 0.5);
+        assert_eq!(translation, Vector::new(-4_900.0, -4_900.0));
+    }
+
+    #[test]
+    fn fit_needs_nodes() {
+        assert_eq!(
+            fit(std::iter::empty(), Size::new(400.0, 400.0), 0.0, 0.5, 2.0),
+            None
+        );
+    }
+}
@@ -0,0 +1,118 @@
+#[derive(Debug, Clone)]
+pub(super) struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+}
+
+impl<T> History<T> {
+    const LIMIT: usize = 100;
+
+    pub fn record(&mut self, inverse: T) {
+        self.redo.clear();
+        self.push_undo(inverse);
+    }
+
+    pub fn undo(&mut self) -> Option<T> {
+        self.undo.pop()
+    }
+
+    pub fn redo(&mut self) -> Option<T> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, inverse: T) {
+        self.undo.push(inverse);
+
+        if self.undo.len() > Self::LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn push_redo(&mut self, inverse: T) {
+        self.redo.push(inverse);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_in_order() {
+        let mut history = History::default();
+
+        history.record(1);
+        history.record(2);
+
+        assert_eq!(history.undo(), Some(2));
+        history.push_redo(-2);
+
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(history.redo(), Some(-2));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn record_clears_redo() {
+        let mut history = History::default();
+
+        history.record(1);
+        history.undo();
+        history.push_redo(-1);
+        assert!(history.can_redo());
+
+        history.record(2);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn undo_is_capped() {
+        let mut history = History::default();
+
+        for step in 0..History::<usize>::LIMIT + 10 {
+            history.record(step);
+        }
+
+        // Redone steps go through the same cap
+        history.push_undo(usize::MAX);
+
+        assert_eq!(history.undo.len(), History::<usize>::LIMIT);
+        assert_eq!(history.undo.first(), Some(&11));
+        assert_eq!(history.undo(), Some(usize::MAX));
+    }
+
+    #[test]
+    fn clear_forgets_everything() {
+        let mut history = History::default();
+
+        history.record(1);
+        history.push_redo(2);
+        history.clear();
+
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+    }
+}
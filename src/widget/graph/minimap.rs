@@ -0,0 +1,69 @@
+use iced_core::{Point, Rectangle, Size, Vector};
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Minimap {
+    bounds: Rectangle,
+    origin: Point,
+    scale: f32,
+}
+
+impl Minimap {
+    const MARGIN: f32 = 10.0;
+    const PADDING: f32 = 50.0;
+
+    pub fn new(editor: Rectangle, size: Size, world: Rectangle) -> Self {
+        let bounds = Rectangle {
+            x: editor.x + editor.width - size.width - Self::MARGIN,
+            y: editor.y + editor.height - size.height - Self::MARGIN,
+            width: size.width,
+            height: size.height,
+        };
+
+        let world = Rectangle {
+            x: world.x - Self::PADDING,
+            y: world.y - Self::PADDING,
+            width: world.width + Self::PADDING * 2.0,
+            height: world.height + Self::PADDING * 2.0,
+        };
+
+        let scale = (bounds.width / world.width).min(bounds.height / world.height);
+
+        // Center the world inside the minimap along its shorter axis
+        let origin = Point::new(
+            world.x - (bounds.width / scale - world.width) / 2.0,
+            world.y - (bounds.height / scale - world.height) / 2.0,
+        );
+
+        Self {
+            bounds,
+            origin,
+            scale,
+        }
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    pub fn project(&self, point: Point) -> Point {
+        Point::new(
+            self.bounds.x + (point.x - self.origin.x) * self.scale,
+            self.bounds.y + (point.y - self.origin.y) * self.scale,
+        )
+    }
+
+    pub fn project_rectangle(&self, rectangle: Rectangle) -> Rectangle {
+        Rectangle::new(
+            self.project(rectangle.position()),
+            Size::new(rectangle.width * self.scale, rectangle.height * self.scale),
+        )
+    }
+
+    pub fn unproject(&self, point: Point) -> Point {
+        self.origin
+            + Vector::new(
+                (point.x - self.bounds.x) / self.scale,
+                (point.y - self.bounds.y) / self.scale,
+            )
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+
+use iced_core::Vector;
+use iced_native::Element;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::edge::Endpoint;
+use super::editor::{self, Editor};
+use super::history::History;
+use super::node::{self, Port, Side};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Id(u64);
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Node<N> {
+    pub payload: N,
+    #[cfg_attr(feature = "serde", serde(with = "vector"))]
+    pub offset: Vector,
+    pub inputs: Vec<Port>,
+    pub outputs: Vec<Port>,
+}
+
+impl<N> Node<N> {
+    pub fn new(payload: N, offset: Vector) -> Self {
+        Self {
+            payload,
+            offset,
+            inputs: vec![Port::new("in", Side::Left)],
+            outputs: vec![Port::new("out", Side::Right)],
+        }
+    }
+
+    pub fn inputs(mut self, inputs: impl IntoIterator<Item = Port>) -> Self {
+        self.inputs = inputs.into_iter().collect();
+        self
+    }
+
+    pub fn outputs(mut self, outputs: impl IntoIterator<Item = Port>) -> Self {
+        self.outputs = outputs.into_iter().collect();
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Edge<E> {
+    pub from: Endpoint<Id>,
+    pub to: Endpoint<Id>,
+    pub payload: E,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Model<N, E> {
+    nodes: BTreeMap<Id, Node<N>>,
+    edges: Vec<Edge<E>>,
+    next_id: u64,
+    pub scaling: f32,
+    #[cfg_attr(feature = "serde", serde(with = "vector"))]
+    pub translation: Vector,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    selection: Vec<Id>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: History<Operation<N, E>>,
+}
+
+#[derive(Debug, Clone)]
+enum Operation<N, E> {
+    Move(Vec<(Id, Vector)>),
+    Connect(Edge<E>),
+    Disconnect(Endpoint<Id>, Endpoint<Id>),
+    Insert(Id, Node<N>, Vec<Edge<E>>),
+    Remove(Id),
+}
+
+impl<N, E> Default for Model<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            edges: vec![],
+            next_id: 0,
+            scaling: 1.0,
+            translation: Vector::new(0.0, 0.0),
+            selection: vec![],
+            history: History::default(),
+        }
+    }
+}
+
+impl<N, E> Model<N, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node(&self, id: Id) -> Option<&Node<N>> {
+        self.nodes.get(&id)
+    }
+
+    pub fn node_mut(&mut self, id: Id) -> Option<&mut Node<N>> {
+        self.nodes.get_mut(&id)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (Id, &Node<N>)> {
+        self.nodes.iter().map(|(id, node)| (*id, node))
+    }
+
+    pub fn edges(&self) -> &[Edge<E>] {
+        &self.edges
+    }
+
+    pub fn selection(&self) -> &[Id] {
+        &self.selection
+    }
+
+    pub fn add_node(&mut self, node: Node<N>) -> Id {
+        let id = Id(self.next_id);
+        self.next_id += 1;
+
+        self.perform(Operation::Insert(id, node, vec![]));
+
+        id
+    }
+
+    pub fn remove_node(&mut self, id: Id) -> bool {
+        self.perform(Operation::Remove(id))
+    }
+
+    pub fn connect(&mut self, from: Endpoint<Id>, to: Endpoint<Id>, payload: E) -> bool {
+        self.perform(Operation::Connect(Edge { from, to, payload }))
+    }
+
+    pub fn disconnect(&mut self, from: Endpoint<Id>, to: Endpoint<Id>) -> bool {
+        self.perform(Operation::Disconnect(from, to))
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self
+            .history
+            .undo()
+            .and_then(|operation| self.apply(operation))
+        {
+            Some(inverse) => {
+                self.history.push_redo(inverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self
+            .history
+            .redo()
+            .and_then(|operation| self.apply(operation))
+        {
+            Some(inverse) => {
+                self.history.push_undo(inverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    pub fn update(&mut self, event: editor::Event<Id>)
+    where
+        E: Default,
+    {
+        match event {
+            editor::Event::NodesMoved(moved) => {
+                self.perform(Operation::Move(moved));
+            }
+            // Viewport changes aren't edits, so they stay out of the history
+            editor::Event::Scaled(scaling, translation) => {
+                self.scaling = scaling;
+                self.translation = translation;
+            }
+            editor::Event::Translated(translation) => {
+                self.translation = translation;
+            }
+            editor::Event::EdgeCreated { from, to } => {
+                self.connect(from, to, E::default());
+            }
+            editor::Event::EdgeSelected { .. } | editor::Event::EdgeLabelClicked { .. } => {}
+            editor::Event::EdgeDeleteRequested { from, to } => {
+                self.disconnect(from, to);
+            }
+            editor::Event::SelectionChanged(selection) => {
+                self.selection = selection;
+            }
+            editor::Event::Undo => {
+                self.undo();
+            }
+            editor::Event::Redo => {
+                self.redo();
+            }
+        }
+    }
+
+    fn position(&self, from: Endpoint<Id>, to: Endpoint<Id>) -> Option<usize> {
+        self.edges
+            .iter()
+            .position(|edge| edge.from == from && edge.to == to)
+    }
+
+    fn perform(&mut self, operation: Operation<N, E>) -> bool {
+        match self.apply(operation) {
+            Some(inverse) => {
+                self.history.record(inverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn apply(&mut self, operation: Operation<N, E>) -> Option<Operation<N, E>> {
+        match operation {
+            Operation::Move(offsets) => {
+                let previous: Vec<_> = offsets
+                    .into_iter()
+                    .filter_map(|(id, offset)| {
+                        let node = self.nodes.get_mut(&id)?;
+
+                        Some((id, std::mem::replace(&mut node.offset, offset)))
+                    })
+                    .collect();
+
+                (!previous.is_empty()).then(|| Operation::Move(previous))
+            }
+            Operation::Connect(edge) => {
+                if !self.nodes.contains_key(&edge.from.node)
+                    || !self.nodes.contains_key(&edge.to.node)
+                    || self.position(edge.from, edge.to).is_some()
+                {
+                    return None;
+                }
+
+                let inverse = Operation::Disconnect(edge.from, edge.to);
+                self.edges.push(edge);
+
+                Some(inverse)
+            }
+            Operation::Disconnect(from, to) => {
+                let index = self.position(from, to)?;
+
+                Some(Operation::Connect(self.edges.remove(index)))
+            }
+            Operation::Insert(id, node, edges) => {
+                self.nodes.insert(id, node);
+                self.edges.extend(edges);
+
+                Some(Operation::Remove(id))
+            }
+            Operation::Remove(id) => {
+                let node = self.nodes.remove(&id)?;
+
+                let (edges, remaining) = std::mem::take(&mut self.edges)
+                    .into_iter()
+                    .partition(|edge| edge.from.node == id || edge.to.node == id);
+
+                self.edges = remaining;
+                self.selection.retain(|selected| *selected != id);
+
+                Some(Operation::Insert(id, node, edges))
+            }
+        }
+    }
+
+    pub fn editor<'a, Message, Renderer>(
+        &'a self,
+        view: impl Fn(Id, &'a N) -> Element<'a, Message, Renderer>,
+        edge: impl Fn(&'a E, super::Edge<Id>) -> super::Edge<Id>,
+        on_event: impl Fn(editor::Event<Id>) -> Message + 'a,
+    ) -> Editor<'a, Id, Message, Renderer>
+    where
+        Renderer: iced_native::Renderer,
+        Renderer::Theme: editor::StyleSheet + node::StyleSheet,
+    {
+        // Group edges by their source once instead of scanning them per node
+        let mut edges: BTreeMap<Id, Vec<super::Edge<Id>>> = BTreeMap::new();
+
+        // The payload decides routing, label and appearance of its edge
+        for record in &self.edges {
+            edges.entry(record.from.node).or_default().push(edge(
+                &record.payload,
+                super::Edge::new(record.from.port, record.to.node, record.to.port),
+            ));
+        }
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(id, node)| {
+                let edges = edges.remove(id).unwrap_or_default();
+
+                super::Node::new(*id, view(*id, &node.payload), node.offset, edges)
+                    .inputs(node.inputs.iter().cloned())
+                    .outputs(node.outputs.iter().cloned())
+            })
+            .collect();
+
+        Editor::new(nodes, on_event)
+            .scaling(self.scaling)
+            .translation(self.translation)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod vector {
+    use iced_core::Vector;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(vector: &Vector, serializer: S) -> Result<S::Ok, S::Error> {
+        (vector.x, vector.y).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vector, D::Error> {
+        let (x, y) = Deserialize::deserialize(deserializer)?;
+
+        Ok(Vector::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Snapshot = (Vec<(Id, Vector)>, Vec<(Endpoint<Id>, Endpoint<Id>, u32)>);
+
+    fn snapshot(model: &Model<&'static str, u32>) -> Snapshot {
+        let mut edges: Vec<_> = model
+            .edges()
+            .iter()
+            .map(|edge| (edge.from, edge.to, edge.payload))
+            .collect();
+
+        // Restored edges are appended, so their order isn't meaningful
+        edges.sort_by_key(|(from, to, _)| (from.node, from.port, to.node, to.port));
+
+        (
+            model.nodes().map(|(id, node)| (id, node.offset)).collect(),
+            edges,
+        )
+    }
+
+    fn graph() -> (Model<&'static str, u32>, Id, Id, Id) {
+        let mut model = Model::new();
+
+        let a = model.add_node(Node::new("a", Vector::new(0.0, 0.0)));
+        let b = model.add_node(Node::new("b", Vector::new(100.0, 0.0)));
+        let c = model.add_node(Node::new("c", Vector::new(200.0, 0.0)));
+
+        model.connect(Endpoint::new(a, 0), Endpoint::new(b, 0), 1);
+        model.connect(Endpoint::new(b, 0), Endpoint::new(c, 0), 2);
+        model.clear_history();
+
+        (model, a, b, c)
+    }
+
+    #[test]
+    fn apply_round_trips() {
+        let (model, a, b, c) = graph();
+
+        let operations = [
+            Operation::Move(vec![(a, Vector::new(5.0, 5.0)), (c, Vector::new(9.0, 1.0))]),
+            Operation::Connect(Edge {
+                from: Endpoint::new(a, 0),
+                to: Endpoint::new(c, 0),
+                payload: 3,
+            }),
+            Operation::Disconnect(Endpoint::new(a, 0), Endpoint::new(b, 0)),
+            Operation::Remove(b),
+        ];
+
+        for operation in operations {
+            let mut model = model.clone();
+            let before = snapshot(&model);
+
+            let inverse = model.apply(operation).unwrap();
+            let after = snapshot(&model);
+            assert_ne!(before, after);
+
+            let redo = model.apply(inverse).unwrap();
+            assert_eq!(snapshot(&model), before);
+
+            model.apply(redo).unwrap();
+            assert_eq!(snapshot(&model), after);
+        }
+    }
+
+    #[test]
+    fn invalid_operations_are_not_recorded() {
+        let (mut model, a, b, _) = graph();
+
+        assert!(!model.connect(Endpoint::new(a, 0), Endpoint::new(b, 0), 9));
+        assert!(!model.disconnect(Endpoint::new(b, 0), Endpoint::new(a, 0)));
+        assert!(!model.can_undo());
+    }
+
+    #[test]
+    fn undo_skips_viewport_changes() {
+        let (mut model, a, _, _) = graph();
+
+        model.update(editor::Event::NodesMoved(vec![(a, Vector::new(40.0, 0.0))]));
+        model.update(editor::Event::Translated(Vector::new(-50.0, 0.0)));
+
+        assert!(model.undo());
+        assert_eq!(model.node(a).unwrap().offset, Vector::new(0.0, 0.0));
+        assert_eq!(model.translation, Vector::new(-50.0, 0.0));
+
+        assert!(model.redo());
+        assert_eq!(model.node(a).unwrap().offset, Vector::new(40.0, 0.0));
+    }
+
+    #[test]
+    fn removing_a_node_restores_its_edges_on_undo() {
+        let (mut model, _, b, _) = graph();
+        let before = snapshot(&model);
+
+        assert!(model.remove_node(b));
+        assert!(model.edges().is_empty());
+
+        assert!(model.undo());
+        assert_eq!(snapshot(&model), before);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut model = Model::new();
+
+        let a = model.add_node(Node::new(String::from("a"), Vector::new(10.0, 20.0)));
+        let b = model.add_node(
+            Node::new(String::from("b"), Vector::new(200.0, 40.0))
+                .inputs([Port::new("x", Side::Top), Port::new("y", Side::Left)]),
+        );
+
+        model.connect(Endpoint::new(a, 0), Endpoint::new(b, 1), 7_u32);
+        model.scaling = 1.5;
+        model.translation = Vector::new(-30.0, 15.0);
+
+        let json = serde_json::to_string(&model).unwrap();
+        let restored: Model<String, u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        assert_eq!(restored.node(b).unwrap().inputs.len(), 2);
+        assert_eq!(restored.edges()[0].payload, 7);
+
+        // Ids keep counting from where the saved model left off
+        let mut restored = restored;
+        let c = restored.add_node(Node::new(String::from("c"), Vector::new(0.0, 0.0)));
+
+        assert!(c != a && c != b);
+    }
+}
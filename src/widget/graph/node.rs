@@ -1,71 +1,200 @@
-use iced_core::{Background, Color, Point, Rectangle, Vector};
-use iced_native::widget::{tree, Tree};
-use iced_native::{event, layout, mouse, renderer, Element, Layout, Shell};
+use iced_core::{Background, Color, Point, Rectangle, Size, Vector};
+use iced_native::widget::{tree, Operation, Tree};
+use iced_native::{event, layout, mouse, overlay, renderer, Element, Layout, Shell};
 
-use super::editor::Event;
+use super::Edge;
 
 #[derive(Debug)]
 pub enum State {
     Idle,
     Hovered,
-    Translating { started_at: Point, offset: Vector },
 }
 
-impl State {
-    pub(super) fn adjusted_bounds(&self, bounds: Rectangle) -> Rectangle {
+impl Default for State {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Side {
+    pub(super) fn direction(self) -> Vector {
+        match self {
+            Side::Left => Vector::new(-1.0, 0.0),
+            Side::Right => Vector::new(1.0, 0.0),
+            Side::Top => Vector::new(0.0, -1.0),
+            Side::Bottom => Vector::new(0.0, 1.0),
+        }
+    }
+
+    pub(super) fn opposite(self) -> Side {
         match self {
-            State::Idle | State::Hovered => bounds,
-            State::Translating { offset, .. } => bounds + *offset,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
         }
     }
 }
 
-impl Default for State {
-    fn default() -> Self {
-        Self::Idle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Port {
+    name: String,
+    side: Side,
+}
+
+impl Port {
+    pub fn new(name: impl Into<String>, side: Side) -> Self {
+        Self {
+            name: name.into(),
+            side,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
     }
 }
 
-pub struct Node<'a, Message, Renderer>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PortKind {
+    Input,
+    Output,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Anchor {
+    pub position: Point,
+    pub side: Side,
+}
+
+pub struct Node<'a, K, Message, Renderer>
 where
     Renderer: iced_native::Renderer,
     Renderer::Theme: StyleSheet,
 {
+    key: K,
     content: Element<'a, Message, Renderer>,
     offset: Vector,
-    pub(super) edges: Vec<usize>,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    pub(super) edges: Vec<Edge<K>>,
     style: <Renderer::Theme as StyleSheet>::Style,
 }
 
-impl<'a, Message, Renderer> Node<'a, Message, Renderer>
+impl<'a, K, Message, Renderer> Node<'a, K, Message, Renderer>
 where
     Renderer: iced_native::Renderer,
     Renderer::Theme: StyleSheet,
 {
     pub fn new(
+        key: K,
         content: impl Into<Element<'a, Message, Renderer>>,
         offset: Vector,
-        edges: Vec<usize>,
+        edges: Vec<Edge<K>>,
     ) -> Self {
         Self {
+            key,
             content: content.into(),
             offset,
+            inputs: vec![Port::new("in", Side::Left)],
+            outputs: vec![Port::new("out", Side::Right)],
             edges,
             style: Default::default(),
         }
     }
 
+    pub fn inputs(mut self, inputs: impl IntoIterator<Item = Port>) -> Self {
+        self.inputs = inputs.into_iter().collect();
+        self
+    }
+
+    pub fn outputs(mut self, outputs: impl IntoIterator<Item = Port>) -> Self {
+        self.outputs = outputs.into_iter().collect();
+        self
+    }
+
     pub fn style(mut self, style: impl Into<<Renderer::Theme as StyleSheet>::Style>) -> Self {
         self.style = style.into();
         self
     }
+
+    pub(super) fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub(super) fn offset(&self) -> Vector {
+        self.offset
+    }
 }
 
-impl<'a, Message, Renderer> Node<'a, Message, Renderer>
+impl<'a, K, Message, Renderer> Node<'a, K, Message, Renderer>
 where
     Renderer: iced_native::Renderer,
     Renderer::Theme: StyleSheet,
 {
+    pub(super) fn ports(&self, kind: PortKind) -> &[Port] {
+        match kind {
+            PortKind::Input => &self.inputs,
+            PortKind::Output => &self.outputs,
+        }
+    }
+
+    pub(super) fn anchor(&self, bounds: Rectangle, kind: PortKind, index: usize) -> Option<Anchor> {
+        let side = match kind {
+            PortKind::Input => self.inputs.get(index)?.side,
+            PortKind::Output => self.outputs.get(index)?.side,
+        };
+
+        // Ports sharing a side are spread evenly along it, inputs first
+        let on_side = |port: &&Port| port.side == side;
+
+        let inputs_on_side = self.inputs.iter().filter(on_side).count();
+        let count = inputs_on_side + self.outputs.iter().filter(on_side).count();
+
+        let position = match kind {
+            PortKind::Input => self.inputs[..index].iter().filter(on_side).count(),
+            PortKind::Output => {
+                inputs_on_side + self.outputs[..index].iter().filter(on_side).count()
+            }
+        };
+
+        let fraction = (position + 1) as f32 / (count + 1) as f32;
+
+        let position = match side {
+            Side::Left => Point::new(bounds.x, bounds.y + bounds.height * fraction),
+            Side::Right => Point::new(bounds.x + bounds.width, bounds.y + bounds.height * fraction),
+            Side::Top => Point::new(bounds.x + bounds.width * fraction, bounds.y),
+            Side::Bottom => {
+                Point::new(bounds.x + bounds.width * fraction, bounds.y + bounds.height)
+            }
+        };
+
+        Some(Anchor { position, side })
+    }
+
+    fn anchors(&self, bounds: Rectangle) -> impl Iterator<Item = Anchor> + '_ {
+        (0..self.inputs.len())
+            .filter_map(move |index| self.anchor(bounds, PortKind::Input, index))
+            .chain(
+                (0..self.outputs.len())
+                    .filter_map(move |index| self.anchor(bounds, PortKind::Output, index)),
+            )
+    }
+
     pub(super) fn tag(&self) -> tree::Tag {
         tree::Tag::of::<State>()
     }
@@ -106,29 +235,34 @@ where
         renderer: &Renderer,
         clipboard: &mut dyn iced_native::Clipboard,
         shell: &mut Shell<'_, Message>,
-        index: usize,
-        on_event: &dyn Fn(Event) -> Message,
     ) -> event::Status {
         let bounds = layout.bounds();
         let content_bounds = layout.children().next().unwrap().bounds();
         let in_bounds =
             bounds.contains(cursor_position) && !content_bounds.contains(cursor_position);
 
+        let status = self.content.as_widget_mut().on_event(
+            tree.children.first_mut().unwrap(),
+            event.clone(),
+            layout.children().next().unwrap(),
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        );
+
         let state = tree.state.downcast_mut::<State>();
 
-        if let State::Translating { started_at, offset } = state {
-            if let iced_native::Event::Mouse(event) = event {
-                match event {
-                    mouse::Event::CursorMoved { .. } => {
-                        *offset = cursor_position - *started_at;
+        if matches!(status, event::Status::Ignored) {
+            if let iced_native::Event::Mouse(mouse::Event::CursorMoved { .. }) = event {
+                match *state {
+                    State::Idle if in_bounds => {
+                        *state = State::Hovered;
                         return event::Status::Captured;
                     }
-                    mouse::Event::ButtonReleased(mouse::Button::Left) => {
-                        shell.publish((on_event)(Event::NodeMoved {
-                            index,
-                            offset: self.offset + *offset,
-                        }));
-                        *state = in_bounds.then_some(State::Hovered).unwrap_or(State::Idle);
+                    State::Hovered if !in_bounds => {
+                        *state = State::Idle;
+                        return event::Status::Captured;
                     }
                     _ => {}
                 }
@@ -136,48 +270,7 @@ where
 
             event::Status::Ignored
         } else {
-            let status = self.content.as_widget_mut().on_event(
-                tree.children.first_mut().unwrap(),
-                event.clone(),
-                layout.children().next().unwrap(),
-                cursor_position,
-                renderer,
-                clipboard,
-                shell,
-            );
-
-            if matches!(status, event::Status::Ignored) {
-                if let iced_native::Event::Mouse(event) = event {
-                    match event {
-                        mouse::Event::CursorMoved { .. }
-                            if in_bounds && matches!(*state, State::Idle) =>
-                        {
-                            *state = State::Hovered;
-                            return event::Status::Captured;
-                        }
-                        mouse::Event::CursorMoved { .. }
-                            if !in_bounds && matches!(*state, State::Hovered) =>
-                        {
-                            *state = State::Idle;
-                            return event::Status::Captured;
-                        }
-                        mouse::Event::ButtonPressed(mouse::Button::Left)
-                            if matches!(*state, State::Hovered) =>
-                        {
-                            *state = State::Translating {
-                                started_at: cursor_position,
-                                offset: Vector::default(),
-                            };
-                            return event::Status::Captured;
-                        }
-                        _ => {}
-                    }
-                }
-
-                event::Status::Ignored
-            } else {
-                status
-            }
+            status
         }
     }
 
@@ -190,45 +283,81 @@ where
         layout: Layout<'_>,
         cursor_position: Point,
         viewport: &Rectangle,
+        selected: bool,
     ) {
-        let state = tree.state.downcast_ref::<State>();
+        let appearance = if selected {
+            theme.selected(self.style)
+        } else {
+            theme.appearance(self.style)
+        };
 
-        let appearance = theme.appearance(self.style);
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: layout.bounds(),
+                border_radius: appearance.border_radius,
+                border_width: appearance.border_width,
+                border_color: appearance.border_color,
+            },
+            appearance
+                .background
+                .unwrap_or_else(|| Color::TRANSPARENT.into()),
+        );
+        self.content.as_widget().draw(
+            tree.children.first().unwrap(),
+            renderer,
+            theme,
+            &renderer::Style {
+                text_color: appearance.text_color.unwrap_or(style.text_color),
+            },
+            layout.children().next().unwrap(),
+            cursor_position,
+            viewport,
+        );
+
+        for anchor in self.anchors(layout.bounds()) {
+            let radius = appearance.port_radius;
 
-        let draw = |renderer: &mut Renderer| {
             renderer.fill_quad(
                 renderer::Quad {
-                    bounds: layout.bounds(),
-                    border_radius: appearance.border_radius,
-                    border_width: appearance.border_width,
-                    border_color: appearance.border_color,
+                    bounds: Rectangle::new(
+                        anchor.position - Vector::new(radius, radius),
+                        Size::new(radius * 2.0, radius * 2.0),
+                    ),
+                    border_radius: radius,
+                    border_width: appearance.port_border_width,
+                    border_color: appearance.port_border_color,
                 },
-                appearance
-                    .background
-                    .unwrap_or_else(|| Color::TRANSPARENT.into()),
+                appearance.port_color,
             );
-            self.content.as_widget().draw(
-                tree.children.first().unwrap(),
-                renderer,
-                theme,
-                &renderer::Style {
-                    text_color: appearance.text_color.unwrap_or(style.text_color),
-                },
-                layout.children().next().unwrap(),
-                cursor_position,
-                viewport,
-            )
-        };
-
-        if let State::Translating { offset, .. } = state {
-            renderer.with_translation(*offset, |renderer| {
-                draw(renderer);
-            });
-        } else {
-            draw(renderer);
         }
     }
 
+    pub(super) fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content.as_widget().operate(
+            tree.children.first_mut().unwrap(),
+            layout.children().next().unwrap(),
+            operation,
+        );
+    }
+
+    pub(super) fn overlay<'b>(
+        &'b self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget().overlay(
+            tree.children.first_mut().unwrap(),
+            layout.children().next().unwrap(),
+            renderer,
+        )
+    }
+
     pub(super) fn mouse_interaction(
         &self,
         tree: &Tree,
@@ -242,7 +371,6 @@ where
         match state {
             State::Idle => mouse::Interaction::default(),
             State::Hovered => mouse::Interaction::Grab,
-            State::Translating { .. } => mouse::Interaction::Grabbing,
         }
     }
 }
@@ -254,6 +382,10 @@ pub struct Appearance {
     pub border_radius: f32,
     pub border_width: f32,
     pub border_color: Color,
+    pub port_radius: f32,
+    pub port_color: Color,
+    pub port_border_width: f32,
+    pub port_border_color: Color,
 }
 
 impl Default for Appearance {
@@ -264,6 +396,10 @@ impl Default for Appearance {
             border_radius: 0.0,
             border_width: 0.0,
             border_color: Color::TRANSPARENT,
+            port_radius: 4.0,
+            port_color: Color::WHITE,
+            port_border_width: 1.0,
+            port_border_color: Color::BLACK,
         }
     }
 }
@@ -272,4 +408,13 @@ pub trait StyleSheet {
     type Style: Default + Copy;
 
     fn appearance(&self, style: Self::Style) -> Appearance;
+
+    fn selected(&self, style: Self::Style) -> Appearance {
+        let appearance = self.appearance(style);
+
+        Appearance {
+            border_width: appearance.border_width + 1.0,
+            ..appearance
+        }
+    }
 }